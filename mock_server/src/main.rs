@@ -0,0 +1,188 @@
+//! A stand-in for the real game server, so the client can be developed without one.
+//!
+//! It answers the endpoints the client uses with canned responses and plays a very simple battle.
+//! Run it with `cargo run -p mock_server`, optionally passing the address to listen on (defaults to `127.0.0.1:3030`)
+//! and a folder to serve assets from (defaults to `mock_server/assets`).
+//! Cards without art in that folder get the button image instead, except for the ones in `CARDS_WITHOUT_ART`
+//! which are there to see how the client deals with missing art.
+//!
+//! Any username and password is accepted.
+mod http;
+
+use card_game_shared::{
+    battle::{BattleErrors, ReturnBattle, TakeAction, TurnResponse},
+    characters::{CharacterCreationResponse, CharacterList},
+};
+use http::{read_request, write_response, Request, Response};
+use serde_json::{json, Value};
+use std::{
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+///Needs to match the protocol version of the client, card_game_shared doesn't export one to share
+const PROTOCOL_VERSION: u32 = 1;
+///Bump this when changing the assets, so clients stop using their cached copies
+const ASSET_VERSION: &str = "1";
+const TOKEN: &str = "mock-token";
+const CHARACTER_ID: i64 = 1;
+const FALLBACK_ART: &[u8] = include_bytes!("../../static/button.png");
+///These never get art, not even the fallback
+const CARDS_WITHOUT_ART: &[&str] = &["focus"];
+//made up for the mock, the real definitions come from the game server
+const CARDS: &[u8] = include_bytes!("../cards.json");
+
+fn new_battle() -> ReturnBattle {
+    ReturnBattle {
+        hand: vec!["fireball".into(), "heal".into(), "shield".into(), "focus".into()],
+        small_runes: vec!["fire".into()],
+        enemy_small_runes: vec!["water".into()],
+        hexa_runes: vec!["earth".into()],
+        player_hp: 30,
+        enemy_hp: 20,
+        mana: 3,
+        enemy_mana: 3,
+        enemy_hand_size: 5,
+    }
+}
+
+fn play(mut battle: ReturnBattle, card: usize) -> TurnResponse {
+    battle.enemy_hp = battle.enemy_hp.saturating_sub(5);
+    battle.player_hp = battle.player_hp.saturating_sub(2);
+    if battle.enemy_hp <= 0 {
+        return TurnResponse::Done;
+    }
+    //rotate the hand, so it is visible that a new turn happened
+    let played = battle.hand.remove(card);
+    battle.hand.push(played);
+    TurnResponse::NextTurn(battle)
+}
+
+struct State {
+    assets: PathBuf,
+    has_character: bool,
+    battle: Option<ReturnBattle>,
+}
+
+fn is_authorized(request: &Request) -> bool {
+    request.headers.get("authorization_token").map(String::as_str) == Some(TOKEN)
+}
+
+fn asset(state: &State, path: &str) -> Response {
+    if path.contains("..") {
+        return Response::error(400, "Invalid path");
+    }
+    let without_art = CARDS_WITHOUT_ART
+        .iter()
+        .any(|id| path == format!("cards/{}.png", id));
+    match std::fs::read(state.assets.join(path)) {
+        Ok(x) => Response::bytes("image/png", x),
+        Err(_) if path.starts_with("cards/") && !without_art => {
+            Response::bytes("image/png", FALLBACK_ART.to_vec())
+        }
+        Err(_) => Response::error(404, "No such asset"),
+    }
+}
+
+fn route(state: &mut State, request: &Request) -> Response {
+    let segments: Vec<_> = request.path.split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("OPTIONS", _) => Response::bytes("text/plain", Vec::new()),
+        ("GET", ["version"]) => Response::json(
+            200,
+            json!({ "protocol_version": PROTOCOL_VERSION, "asset_version": ASSET_VERSION }),
+        ),
+        ("POST", ["login"]) | ("POST", ["register"]) => {
+            Response::json(200, json!({ "success": true, "token": TOKEN }))
+        }
+        ("GET", ["assets", "cards.json"]) => Response::bytes("application/json", CARDS.to_vec()),
+        ("GET", ["assets", "manifest.json"]) => Response::json(
+            200,
+            json!({ "files": ["cards/fireball.png", "cards/heal.png", "cards/shield.png"] }),
+        ),
+        ("GET", ["assets", ..]) => asset(state, &request.path["assets/".len()..]),
+        _ if !is_authorized(request) => Response::json(
+            401,
+            json!({ "code": "unauthorized", "message": "Not logged in" }),
+        ),
+        ("POST", ["logout"]) => Response::json(200, Value::Null),
+        ("GET", ["characters"]) => {
+            let characters = if state.has_character {
+                vec![CHARACTER_ID]
+            } else {
+                Vec::new()
+            };
+            Response::json(200, json!(CharacterList { characters }))
+        }
+        ("POST", ["characters"]) => {
+            state.has_character = true;
+            Response::json(200, json!(CharacterCreationResponse { id: CHARACTER_ID }))
+        }
+        ("POST", ["battle", _]) => {
+            let battle = new_battle();
+            let res = json!(battle);
+            state.battle = Some(battle);
+            Response::json(200, res)
+        }
+        ("GET", ["battle", _]) => match &state.battle {
+            Some(x) => Response::json(200, json!(x)),
+            None => Response::error(404, "No battle going on"),
+        },
+        ("PUT", ["battle", ""]) | ("PUT", ["battle"]) => {
+            let action: TakeAction = match serde_json::from_slice(&request.body) {
+                Ok(x) => x,
+                Err(_) => return Response::error(400, "Invalid action"),
+            };
+            let res = match state.battle.take() {
+                Some(x) if action.play_card < x.hand.len() => play(x, action.play_card),
+                Some(x) => {
+                    state.battle = Some(x);
+                    TurnResponse::Error(BattleErrors::ChosenCardNotInHand(action.play_card))
+                }
+                None => return Response::error(404, "No battle going on"),
+            };
+            let body = json!(res);
+            //a finished battle is gone, otherwise the new turn is the battle from now on
+            if let TurnResponse::NextTurn(x) = res {
+                state.battle = Some(x);
+            }
+            Response::json(200, body)
+        }
+        _ => Response::error(404, "Unknown endpoint"),
+    }
+}
+
+fn handle(state: &Mutex<State>, stream: TcpStream) -> std::io::Result<()> {
+    let request = read_request(&stream)?;
+    let response = route(&mut state.lock().unwrap(), &request);
+    println!("{} /{} -> {}", request.method, request.path, response.status);
+    write_response(&stream, response)
+}
+
+fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:3030".into());
+    let assets = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("mock_server/assets"));
+    let state = Arc::new(Mutex::new(State {
+        assets,
+        has_character: false,
+        battle: None,
+    }));
+    let listener = TcpListener::bind(&address)?;
+    println!("Mock server listening on http://{}", address);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let state = state.clone();
+        thread::spawn(move || {
+            if let Err(x) = handle(&state, stream) {
+                eprintln!("Failed to handle request: {}", x);
+            }
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use super::lru::Lru;
+use crate::{Result, APP_NAME};
+use quicksilver::saving::{load, save, Location};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const INDEX: &str = "asset_cache";
+///Roughly how many bytes of assets get kept around by default
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_MAX_BYTES: usize = 20 * 1024 * 1024;
+//browsers tend to give a site about 5 MB of local storage, which the saves and the cassette need some of as well
+#[cfg(target_arch = "wasm32")]
+const DEFAULT_MAX_BYTES: usize = 3 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Entry {
+    version: String,
+    ///How much space the stored file takes up, rather than the asset itself
+    size: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Index {
+    entries: HashMap<String, Entry>,
+    lru: Lru,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredAsset {
+    key: String,
+    ///Base64, as an array of numbers takes up 3 to 4 times as much space
+    bytes: String,
+}
+
+fn key(server: &str, path: &str) -> String {
+    format!("{}|{}", server, path)
+}
+
+//the key contains urls, which don't make for good file names
+fn file_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|v| if v.is_ascii_alphanumeric() { v } else { '_' })
+        .collect();
+    format!("asset_{}", name)
+}
+
+///Keeps downloaded assets between sessions, so card art doesn't get downloaded again every time the game starts.
+///
+///Every asset is stored with the asset version the server reported at the time,
+///and is only used again while the server still reports that same version.
+///Once the cache grows beyond `max_bytes` or storage runs out of space, the assets that were used the longest ago get removed.
+pub(crate) struct AssetCache {
+    index: Index,
+    //assets got used since the index was last saved, see `save_usage`
+    unsaved_usage: bool,
+    pub(crate) max_bytes: usize,
+}
+
+impl AssetCache {
+    pub(crate) fn load() -> Self {
+        Self {
+            index: load(Location::Cache, APP_NAME, INDEX).unwrap_or_default(),
+            unsaved_usage: false,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+    fn save_index(&mut self) -> Result<()> {
+        save(Location::Cache, APP_NAME, INDEX, &self.index)?;
+        self.unsaved_usage = false;
+        Ok(())
+    }
+    ///Saves which assets got used since the last save.
+    ///`get` doesn't do this itself, as writing the index for every asset would be a lot of writes for very little
+    pub(crate) fn save_usage(&mut self) -> Result<()> {
+        if self.unsaved_usage {
+            self.save_index()?;
+        }
+        Ok(())
+    }
+    //there is no way to delete a save, so it gets overwritten with nothing instead
+    fn remove_file(key: &str) -> Result<()> {
+        save(Location::Cache, APP_NAME, &file_name(key), &())?;
+        Ok(())
+    }
+    ///How many bytes of assets are currently stored
+    pub(crate) fn size(&self) -> usize {
+        self.index.entries.values().map(|v| v.size).sum()
+    }
+    pub(crate) fn get(&mut self, server: &str, path: &str, version: &str) -> Option<Vec<u8>> {
+        let key = key(server, path);
+        let up_to_date = self.index.entries.get(&key)?.version == version;
+        let stored = if up_to_date {
+            load::<StoredAsset>(Location::Cache, APP_NAME, &file_name(&key))
+                .ok()
+                .filter(|v| v.key == key)
+        } else {
+            None
+        };
+        match stored {
+            Some(stored) => {
+                self.index.lru.touch(&key);
+                self.unsaved_usage = true;
+                base64::decode(stored.bytes).ok()
+            }
+            None => {
+                let _ = self.remove(&key);
+                None
+            }
+        }
+    }
+    pub(crate) fn insert(&mut self, server: &str, path: &str, version: &str, bytes: &[u8]) -> Result<()> {
+        let key = key(server, path);
+        let stored = StoredAsset {
+            key: key.clone(),
+            bytes: base64::encode(bytes),
+        };
+        //what counts against the limit is what ends up on disk, not the size of the asset itself
+        let size = serde_json::to_string(&stored)?.len();
+        if size > self.max_bytes {
+            return Ok(());
+        }
+        //an older copy of this asset gets replaced, so it doesn't need room of its own
+        let replaced = self.index.entries.get(&key).map_or(0, |v| v.size);
+        //make room beforehand, so storage doesn't fill up with assets that are about to be evicted anyway
+        while self.size() - replaced + size > self.max_bytes && self.evict_oldest(&key)? {}
+        //storage can still run out before max_bytes is reached, in which case older assets have to make way
+        while let Err(x) = save(Location::Cache, APP_NAME, &file_name(&key), &stored) {
+            if !self.evict_oldest(&key)? {
+                return Err(x.into());
+            }
+        }
+        self.index.lru.touch(&key);
+        self.index.entries.insert(
+            key,
+            Entry {
+                version: version.to_owned(),
+                size,
+            },
+        );
+        self.save_index()
+    }
+    //returns false if there was nothing left to evict besides `keep`
+    fn evict_oldest(&mut self, keep: &str) -> Result<bool> {
+        match self.index.lru.oldest(|v| v == keep) {
+            Some(x) => {
+                self.index.entries.remove(&x);
+                self.index.lru.remove(&x);
+                Self::remove_file(&x)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+    ///Removes an asset, like one that turned out to be broken
+    pub(crate) fn forget(&mut self, server: &str, path: &str) -> Result<()> {
+        self.remove(&key(server, path))
+    }
+    fn remove(&mut self, key: &str) -> Result<()> {
+        if self.index.entries.remove(key).is_some() {
+            self.index.lru.remove(key);
+            Self::remove_file(key)?;
+            self.save_index()?;
+        }
+        Ok(())
+    }
+    pub(crate) fn clear(&mut self) -> Result<()> {
+        for key in self.index.entries.keys() {
+            Self::remove_file(key)?;
+        }
+        self.index.entries.clear();
+        self.index.lru.clear();
+        self.save_index()
+    }
+}
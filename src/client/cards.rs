@@ -0,0 +1,83 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct CardDefinition {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    ///How much mana it takes to play the card
+    pub(crate) cost: u64,
+    #[serde(default)]
+    pub(crate) description: String,
+    ///Names of keywords the rules text relies on, see `CardRegistry::keyword`
+    #[serde(default)]
+    pub(crate) keywords: Vec<String>,
+}
+
+//the format of cards.json on the server
+#[derive(Deserialize, Debug)]
+pub(crate) struct CardFile {
+    pub(crate) cards: Vec<CardDefinition>,
+    #[serde(default)]
+    pub(crate) keywords: HashMap<String, String>,
+}
+
+///Everything the client knows about cards besides their art, queried by card id.
+///Empty until `Client::load_card_definitions` ran.
+#[derive(Debug, Default)]
+pub(crate) struct CardRegistry {
+    cards: HashMap<String, CardDefinition>,
+    keywords: HashMap<String, String>,
+}
+
+impl CardRegistry {
+    pub(crate) fn get(&self, id: &str) -> Option<&CardDefinition> {
+        self.cards.get(id)
+    }
+    ///The explanation of a keyword used in the rules text of cards
+    pub(crate) fn keyword(&self, name: &str) -> Option<&str> {
+        self.keywords.get(name).map(String::as_str)
+    }
+}
+
+impl From<CardFile> for CardRegistry {
+    fn from(file: CardFile) -> Self {
+        Self {
+            cards: file
+                .cards
+                .into_iter()
+                .map(|v| (v.id.clone(), v))
+                .collect(),
+            keywords: file.keywords,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardFile, CardRegistry};
+    use serde_json::json;
+
+    #[test]
+    fn card_definitions_can_be_queried() {
+        let file: CardFile = serde_json::from_value(json!({
+            "cards": [
+                {"id": "fireball", "name": "Fireball", "cost": 2,
+                    "description": "Deal 5 damage. Burn.", "keywords": ["burn"]},
+                {"id": "heal", "name": "Heal", "cost": 1},
+            ],
+            "keywords": {"burn": "Deals 1 damage every turn."}
+        }))
+        .unwrap();
+        let cards = CardRegistry::from(file);
+        let fireball = cards.get("fireball").unwrap();
+        assert_eq!(fireball.name, "Fireball");
+        assert_eq!(fireball.cost, 2);
+        assert!(!fireball.description.is_empty());
+        for keyword in &fireball.keywords {
+            assert!(cards.keyword(keyword).is_some());
+        }
+        assert!(cards.get("heal").unwrap().keywords.is_empty());
+        assert!(cards.get("not a card").is_none());
+    }
+}
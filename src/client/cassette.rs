@@ -0,0 +1,273 @@
+use super::{
+    transport::Response as TransportResponse, HttpMethod, Request, SilverSurf, Transport,
+};
+use crate::APP_NAME;
+use async_trait::async_trait;
+use quicksilver::{
+    load_file,
+    saving::{save, Location},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+const CASSETTE_PROFILE: &str = "cassette";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+enum Body {
+    Json(serde_json::Value),
+    ///Base64, assets like card art aren't json and would be huge as an array of numbers
+    Raw(String),
+}
+
+impl Body {
+    fn new(bytes: &[u8]) -> Self {
+        match serde_json::from_slice(bytes) {
+            Ok(mut x) => {
+                redact(&mut x);
+                Body::Json(x)
+            }
+            Err(_) => Body::Raw(base64::encode(bytes)),
+        }
+    }
+    fn into_bytes(self) -> crate::Result<Vec<u8>> {
+        Ok(match self {
+            Body::Json(x) => x.to_string().into_bytes(),
+            Body::Raw(x) => base64::decode(x)?,
+        })
+    }
+}
+
+//cassettes get shared around, so they should never contain anything that lets someone log in as whoever recorded it
+const SECRET_FIELDS: &[&str] = &["password", "token"];
+
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(x) => {
+            for (key, value) in x.iter_mut() {
+                if SECRET_FIELDS.contains(&key.as_str()) {
+                    *value = serde_json::Value::String("redacted".into());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        serde_json::Value::Array(x) => x.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+///A single request and the response the server gave to it.
+///Headers are left out and passwords and tokens in bodies are redacted,
+///so a cassette never contains the credentials of whoever recorded it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Interaction {
+    method: HttpMethod,
+    path: String,
+    request: Option<serde_json::Value>,
+    status: u16,
+    response: Body,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub(crate) struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+//only the path is stored, so a cassette recorded against one server can be replayed with any server address
+fn path_of(url: &str) -> String {
+    let without_scheme = url.splitn(2, "://").last().unwrap_or(url);
+    match without_scheme.find('/') {
+        Some(x) => without_scheme[x + 1..].to_owned(),
+        None => String::new(),
+    }
+}
+
+///Passes every request on to another transport, writing what happened to the "cassette" save file.
+///The cassette is saved after every response, so it is complete even if the client crashes.
+///Probes are passed on without being written down, they can go to any server and would just be noise.
+pub(crate) struct Recorder<T: Transport> {
+    inner: T,
+    cassette: RefCell<Cassette>,
+}
+
+impl<T: Transport> Recorder<T> {
+    pub(crate) fn new(inner: T) -> Self {
+        Self {
+            inner,
+            cassette: RefCell::new(Cassette::default()),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Transport for Recorder<T> {
+    async fn send(&self, request: Request) -> crate::Result<TransportResponse> {
+        let method = request.method;
+        let path = path_of(&request.url);
+        let mut body = request.body.clone();
+        if let Some(body) = &mut body {
+            redact(body);
+        }
+        let res = self.inner.send(request).await?;
+        let mut cassette = self.cassette.borrow_mut();
+        cassette.interactions.push(Interaction {
+            method,
+            path,
+            request: body,
+            status: res.status,
+            response: Body::new(&res.body),
+        });
+        //a cassette silently missing responses only shows up once it gets replayed, so better to fail right away
+        save(Location::Data, APP_NAME, CASSETTE_PROFILE, &*cassette)
+            .map_err(|x| format!("Could not save the cassette: {}", x))?;
+        Ok(res)
+    }
+    async fn probe(&self, request: Request) -> crate::Result<TransportResponse> {
+        self.inner.probe(request).await
+    }
+}
+
+///Answers requests with the responses from a cassette, in the order they were recorded.
+///
+///Once every recorded response to a GET request is used up, the last one keeps being given out.
+///That way things like the server status indicator don't run the cassette dry.
+pub(crate) struct Replayer {
+    //whether the interaction got replayed already
+    interactions: RefCell<Vec<(bool, Interaction)>>,
+}
+
+impl Replayer {
+    pub(crate) fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: RefCell::new(
+                cassette
+                    .interactions
+                    .into_iter()
+                    .map(|v| (false, v))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Transport for Replayer {
+    async fn send(&self, request: Request) -> crate::Result<TransportResponse> {
+        let path = path_of(&request.url);
+        let is_match = |v: &Interaction| v.method == request.method && v.path == path;
+        let mut interactions = self.interactions.borrow_mut();
+        let position = interactions
+            .iter()
+            .position(|(used, v)| !used && is_match(v))
+            .or_else(|| match request.method {
+                HttpMethod::Get => interactions.iter().rposition(|(_, v)| is_match(v)),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                format!(
+                    "The cassette has no more responses for {:?} /{}",
+                    request.method, path
+                )
+            })?;
+        let (used, interaction) = &mut interactions[position];
+        *used = true;
+        Ok(TransportResponse {
+            status: interaction.status,
+            body: interaction.response.clone().into_bytes()?,
+        })
+    }
+}
+
+///The transport the app runs with, picked at startup
+pub(crate) enum SessionTransport {
+    Live(SilverSurf),
+    Record(Recorder<SilverSurf>),
+    Replay(Replayer),
+}
+
+impl SessionTransport {
+    ///Picks the transport based on `cassette_mode.txt`, which contains either `record` or `replay`.
+    ///Without that file the client just talks to the server.
+    ///
+    ///Recording saves the cassette as "cassette" in the data folder of the game (local storage on the web).
+    ///Replaying reads `cassette.json` next to the other assets, so a recorded cassette can be dropped in there.
+    pub(crate) async fn load() -> crate::Result<Self> {
+        let mode = match load_file("cassette_mode.txt").await {
+            Ok(x) => String::from_utf8(x)?.trim().to_lowercase(),
+            Err(_) => return Ok(SessionTransport::Live(SilverSurf)),
+        };
+        match mode.as_str() {
+            "" | "live" => Ok(SessionTransport::Live(SilverSurf)),
+            "record" => Ok(SessionTransport::Record(Recorder::new(SilverSurf))),
+            "replay" => {
+                let cassette = serde_json::from_slice(&load_file("cassette.json").await?)?;
+                Ok(SessionTransport::Replay(Replayer::new(cassette)))
+            }
+            x => Err(format!("Unknown cassette mode \"{}\", expected record or replay", x).into()),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Transport for SessionTransport {
+    async fn send(&self, request: Request) -> crate::Result<TransportResponse> {
+        match self {
+            SessionTransport::Live(x) => x.send(request).await,
+            SessionTransport::Record(x) => x.send(request).await,
+            SessionTransport::Replay(x) => x.send(request).await,
+        }
+    }
+    async fn probe(&self, request: Request) -> crate::Result<TransportResponse> {
+        match self {
+            SessionTransport::Live(x) => x.probe(request).await,
+            SessionTransport::Record(x) => x.probe(request).await,
+            SessionTransport::Replay(x) => x.probe(request).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{redact, Body, Recorder};
+    use crate::client::{HttpMethod, MemoryTransport, Request, Transport};
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    #[test]
+    fn credentials_are_redacted() {
+        let mut login = json!({"username": "user", "password": "hunter2"});
+        redact(&mut login);
+        assert_eq!(login, json!({"username": "user", "password": "redacted"}));
+        match Body::new(br#"{"success": true, "token": "secret"}"#) {
+            Body::Json(x) => assert_eq!(x, json!({"success": true, "token": "redacted"})),
+            x => panic!("Expected a json body, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn raw_bodies_survive_a_round_trip() {
+        let bytes = vec![0x89, b'P', b'N', b'G', 0, 255];
+        let body = Body::new(&bytes);
+        assert!(matches!(body, Body::Raw(_)));
+        let saved = serde_json::to_string(&body).unwrap();
+        let loaded: Body = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.into_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn probes_are_not_recorded() {
+        let recorder = Recorder::new(MemoryTransport::new());
+        recorder
+            .inner
+            .respond(HttpMethod::Get, "http://other/version", 404, Vec::new());
+        let res = block_on(recorder.probe(Request {
+            method: HttpMethod::Get,
+            url: "http://other/version".into(),
+            body: None,
+            headers: Vec::new(),
+        }))
+        .unwrap();
+        assert_eq!(res.status, 404);
+        assert!(recorder.cassette.borrow().interactions.is_empty());
+    }
+}
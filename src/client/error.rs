@@ -0,0 +1,107 @@
+use crate::responses::ErrorRes;
+use std::{error::Error, fmt};
+
+#[derive(Debug)]
+pub(crate) enum ClientError {
+    ///The request never got a response, the server is most likely unreachable
+    Transport(crate::Error),
+    ///The server took longer to respond than we were willing to wait
+    Timeout,
+    ///The server responded with a status code that isn't a success and no error we understand
+    Status(u16),
+    ///The server understood the request but refused it, explaining why
+    Server {
+        ///The status code the explanation came with
+        status: u16,
+        code: Option<String>,
+        message: String,
+    },
+    ///The server responded with something that doesn't match what we expected
+    Decode(serde_json::Error),
+    ///The server speaks a different version of the protocol than we do
+    Incompatible { client: u32, server: u32 },
+    ///An asset got downloaded but could not be turned into something usable
+    Asset {
+        path: String,
+        error: quicksilver::QuicksilverError,
+    },
+}
+
+pub(crate) type ClientResult<T> = std::result::Result<T, ClientError>;
+
+impl ClientError {
+    ///Whether the server refused our token, meaning the user has to log in again
+    pub(crate) fn is_session_expired(&self) -> bool {
+        match self {
+            ClientError::Status(401) | ClientError::Server { status: 401, .. } => true,
+            ClientError::Server {
+                code: Some(code), ..
+            } => code == "unauthorized" || code == "session_expired",
+            _ => false,
+        }
+    }
+    ///Turns an error the server explained into a `ClientError`, keeping the status it came with
+    pub(crate) fn from_error_res(status: u16, from: ErrorRes) -> Self {
+        match from {
+            ErrorRes::WithCode { code, message } => ClientError::Server {
+                status,
+                code: Some(code),
+                message,
+            },
+            ErrorRes::Basic { message } => ClientError::Server {
+                status,
+                code: None,
+                message,
+            },
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(from: serde_json::Error) -> Self {
+        ClientError::Decode(from)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(x) => write!(f, "Could not reach the server: {}", x),
+            ClientError::Timeout => write!(f, "The server took too long to respond"),
+            ClientError::Status(x) => write!(f, "Server responded with status {}", x),
+            ClientError::Server {
+                code: Some(code),
+                message,
+                ..
+            } => write!(f, "{} ({})", message, code),
+            ClientError::Server {
+                code: None,
+                message,
+                ..
+            } => write!(f, "{}", message),
+            ClientError::Decode(x) => write!(f, "Could not understand the server: {}", x),
+            ClientError::Incompatible { client, server } => write!(
+                f,
+                "Client speaks protocol version {} but the server speaks {}",
+                client, server
+            ),
+            ClientError::Asset { path, error } => {
+                write!(f, "Could not load asset {}: {}", path, error)
+            }
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Transport(x) => Some(x.as_ref()),
+            ClientError::Decode(x) => Some(x),
+            ClientError::Asset { error, .. } => Some(error),
+            ClientError::Timeout
+            | ClientError::Status(_)
+            | ClientError::Server { .. }
+            | ClientError::Incompatible { .. } => None,
+        }
+    }
+}
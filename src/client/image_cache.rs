@@ -0,0 +1,201 @@
+use super::lru::Lru;
+use quicksilver::{graphics::Image, Graphics, QuicksilverError};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+///How much the image cache may hold before it starts evicting the least recently used images
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ImageBudget {
+    Count(usize),
+    ///Estimated from the size of the images, assuming 4 bytes per pixel
+    TextureBytes(usize),
+}
+
+impl Default for ImageBudget {
+    fn default() -> Self {
+        ImageBudget::TextureBytes(64 * 1024 * 1024)
+    }
+}
+
+///Whatever the image cache stores, so it can be used without a graphics context
+pub(crate) trait TextureSize: Clone {
+    fn texture_bytes(&self) -> usize;
+}
+
+impl TextureSize for Image {
+    fn texture_bytes(&self) -> usize {
+        let size = self.size();
+        size.x as usize * size.y as usize * 4
+    }
+}
+
+///Turns downloaded files into images, which the game does with its graphics context
+pub(crate) trait ImageDecoder {
+    type Image: TextureSize;
+    fn decode(&self, bytes: &[u8]) -> Result<Self::Image, QuicksilverError>;
+}
+
+impl ImageDecoder for Graphics {
+    type Image = Image;
+    fn decode(&self, bytes: &[u8]) -> Result<Image, QuicksilverError> {
+        Image::from_encoded_bytes(self, bytes)
+    }
+}
+
+///Stands in for an image in tests, as those have no graphics context to make real ones with
+#[cfg(test)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FakeImage(pub(crate) usize);
+
+#[cfg(test)]
+impl TextureSize for FakeImage {
+    fn texture_bytes(&self) -> usize {
+        self.0
+    }
+}
+
+///Decodes every file into a `FakeImage` as big as the file
+#[cfg(test)]
+pub(crate) struct FakeDecoder;
+
+#[cfg(test)]
+impl ImageDecoder for FakeDecoder {
+    type Image = FakeImage;
+    fn decode(&self, bytes: &[u8]) -> Result<FakeImage, QuicksilverError> {
+        Ok(FakeImage(bytes.len()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CacheStats {
+    pub(crate) hits: u64,
+    pub(crate) misses: u64,
+    pub(crate) evictions: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} evictions",
+            self.hits, self.misses, self.evictions
+        )
+    }
+}
+
+///Keeps loaded images in memory, evicting the least recently used ones once it goes over its budget.
+///Pinned images, like the cards currently in hand, are never evicted, even if that means going over budget.
+pub(crate) struct ImageCache<V: TextureSize = Image> {
+    images: HashMap<String, V>,
+    lru: Lru,
+    pinned: HashSet<String>,
+    pub(crate) budget: ImageBudget,
+    stats: CacheStats,
+}
+
+impl<V: TextureSize> ImageCache<V> {
+    pub(crate) fn new(budget: ImageBudget) -> Self {
+        Self {
+            images: HashMap::new(),
+            lru: Lru::default(),
+            pinned: HashSet::new(),
+            budget,
+            stats: CacheStats::default(),
+        }
+    }
+    pub(crate) fn stats(&self) -> CacheStats {
+        self.stats
+    }
+    ///Checks if an image is cached, counting it as a hit or a miss and marking it as used
+    pub(crate) fn touch(&mut self, path: &str) -> bool {
+        if self.images.contains_key(path) {
+            self.lru.touch(path);
+            self.stats.hits += 1;
+            true
+        } else {
+            self.stats.misses += 1;
+            false
+        }
+    }
+    ///Gets an image without it counting towards the stats
+    pub(crate) fn peek(&self, path: &str) -> Option<V> {
+        self.images.get(path).cloned()
+    }
+    pub(crate) fn insert(&mut self, path: String, image: V) {
+        self.lru.touch(&path);
+        self.images.insert(path, image);
+        self.evict();
+    }
+    ///Replaces the set of images that may not be evicted
+    pub(crate) fn pin(&mut self, paths: impl IntoIterator<Item = String>) {
+        self.pinned = paths.into_iter().collect();
+        self.evict();
+    }
+    fn is_over_budget(&self) -> bool {
+        match self.budget {
+            ImageBudget::Count(max) => self.images.len() > max,
+            ImageBudget::TextureBytes(max) => {
+                self.images
+                    .values()
+                    .map(TextureSize::texture_bytes)
+                    .sum::<usize>()
+                    > max
+            }
+        }
+    }
+    fn evict(&mut self) {
+        while self.is_over_budget() {
+            let pinned = &self.pinned;
+            match self.lru.oldest(|v| pinned.contains(v)) {
+                Some(path) => {
+                    self.images.remove(&path);
+                    self.lru.remove(&path);
+                    self.stats.evictions += 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheStats, FakeImage, ImageBudget, ImageCache};
+
+    #[test]
+    fn least_recently_used_images_get_evicted() {
+        let mut cache = ImageCache::new(ImageBudget::Count(2));
+        cache.insert("a".into(), FakeImage(1));
+        cache.insert("b".into(), FakeImage(1));
+        assert!(cache.touch("a"));
+        cache.insert("c".into(), FakeImage(1));
+        assert!(cache.touch("a"));
+        assert!(!cache.touch("b"));
+        assert!(cache.touch("c"));
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 3,
+                misses: 1,
+                evictions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn images_in_hand_are_never_evicted() {
+        let mut cache = ImageCache::new(ImageBudget::TextureBytes(100));
+        cache.pin(vec!["hand".to_owned()]);
+        cache.insert("hand".into(), FakeImage(80));
+        cache.insert("other".into(), FakeImage(80));
+        assert!(cache.peek("hand").is_some());
+        assert!(cache.peek("other").is_none());
+        //with only pinned images left, going over budget is better than dropping a card in hand
+        cache.pin(vec!["hand".to_owned(), "hand2".to_owned()]);
+        cache.insert("hand2".into(), FakeImage(80));
+        assert!(cache.peek("hand").is_some());
+        assert!(cache.peek("hand2").is_some());
+    }
+}
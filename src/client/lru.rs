@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+///Remembers when every key was last used, so caches know which entry to evict first.
+///It only keeps track of the order, storing the entries themselves is up to the cache
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub(crate) struct Lru {
+    last_used: HashMap<String, u64>,
+    //goes up every time a key is used, so the least recently used one can be found
+    clock: u64,
+}
+
+impl Lru {
+    ///Marks a key as the most recently used one, starting to keep track of it if needed
+    pub(crate) fn touch(&mut self, key: &str) {
+        self.clock += 1;
+        match self.last_used.get_mut(key) {
+            Some(x) => *x = self.clock,
+            None => {
+                self.last_used.insert(key.to_owned(), self.clock);
+            }
+        }
+    }
+    pub(crate) fn remove(&mut self, key: &str) {
+        self.last_used.remove(key);
+    }
+    pub(crate) fn clear(&mut self) {
+        self.last_used.clear();
+    }
+    ///The key that was used the longest ago, leaving out the ones `keep` returns true for
+    pub(crate) fn oldest(&self, keep: impl Fn(&str) -> bool) -> Option<String> {
+        self.last_used
+            .iter()
+            .filter(|(key, _)| !keep(key))
+            .min_by_key(|(_, last_used)| **last_used)
+            .map(|(key, _)| key.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lru;
+
+    #[test]
+    fn oldest_skips_kept_keys() {
+        let mut lru = Lru::default();
+        lru.touch("a");
+        lru.touch("b");
+        lru.touch("c");
+        lru.touch("a");
+        assert_eq!(lru.oldest(|_| false).as_deref(), Some("b"));
+        assert_eq!(lru.oldest(|v| v == "b").as_deref(), Some("c"));
+        lru.remove("c");
+        assert_eq!(lru.oldest(|v| v == "b").as_deref(), Some("a"));
+        lru.clear();
+        assert_eq!(lru.oldest(|_| false), None);
+    }
+}
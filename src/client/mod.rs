@@ -0,0 +1,653 @@
+mod asset_cache;
+mod cards;
+mod cassette;
+mod error;
+mod image_cache;
+mod lru;
+mod retry;
+mod transport;
+
+use crate::responses::{AssetManifest, CustomResult, ErrorRes, LoginResponse, VersionResponse};
+pub(crate) use asset_cache::AssetCache;
+pub(crate) use cards::CardRegistry;
+pub(crate) use cassette::SessionTransport;
+pub(crate) use error::{ClientError, ClientResult};
+#[cfg(test)]
+pub(crate) use image_cache::{FakeDecoder, FakeImage};
+use image_cache::{ImageBudget, ImageCache};
+pub(crate) use image_cache::{ImageDecoder, TextureSize};
+pub(crate) use retry::RetryPolicy;
+#[cfg(test)]
+pub(crate) use transport::MemoryTransport;
+pub(crate) use transport::{HttpMethod, Request, SilverSurf, Transport};
+
+#[cfg(test)]
+mod tests;
+
+use card_game_shared::{
+    battle::{BattleErrors, ReturnBattle, TakeAction, TurnResponse},
+    characters::{CharacterCreationResponse, CharacterList},
+    users::LoginData,
+};
+use futures::{future::join_all, FutureExt};
+use instant::{Duration, Instant};
+use cards::CardFile;
+use quicksilver::graphics::Image;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, convert::TryInto, future::Future, pin::Pin, rc::Rc};
+
+///The version of the protocol spoken by the revision of card_game_shared that Cargo.toml is pinned to.
+///Bump this together with that revision, card_game_shared doesn't export a version of its own.
+pub(crate) const PROTOCOL_VERSION: u32 = 1;
+
+fn join_url(base_url: &str, part: &str) -> String {
+    if base_url.ends_with('/') {
+        format!("{}{}", base_url, part)
+    } else {
+        format!("{}/{}", base_url, part)
+    }
+}
+
+fn is_success(status: u16) -> bool {
+    (200..300).contains(&status)
+}
+
+const CARDS_FILE: &str = "cards.json";
+
+fn card_art_path(id: &str) -> String {
+    format!("cards/{}.png", id)
+}
+
+///Mana as the server sends it, where anything below zero counts as having none
+pub(crate) fn mana_amount<N: TryInto<u64>>(mana: N) -> u64 {
+    mana.try_into().unwrap_or(0)
+}
+
+//art that is missing now is likely still missing in a moment, so back off up to a minute between tries
+fn art_retry_delay(attempts: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempts + 1).min(60))
+}
+
+fn decode<T: DeserializeOwned>(status: u16, body: &[u8]) -> ClientResult<T> {
+    match serde_json::from_slice::<CustomResult<T>>(body) {
+        Ok(x) => x.into_client_res(status),
+        //if the status already tells us something went wrong, that is more useful than whatever serde has to say
+        Err(_) if !is_success(status) => Err(ClientError::Status(status)),
+        Err(x) => Err(x.into()),
+    }
+}
+
+pub enum AfterTurn<T = ReturnBattleWithImages> {
+    Over,
+    NewTurn(T),
+    ///The card cost more mana than the player had
+    NoTurnHappened,
+    OutOfSync,
+}
+///Card art that is being downloaded again, see `Client::retry_card_art`
+pub(crate) struct PendingArt {
+    ids: Vec<String>,
+    download: Pin<Box<dyn Future<Output = Vec<(String, ClientResult<Vec<u8>>)>>>>,
+}
+///An image being loaded ahead of time, see `Client::preload_image`
+pub(crate) struct PendingImage {
+    path: String,
+    //whether it comes from the server, rather than from the asset cache
+    downloading: bool,
+    load: Pin<Box<dyn Future<Output = ClientResult<Vec<u8>>>>>,
+}
+pub struct ReturnBattleWithImages<I = Image> {
+    ///`None` for cards whose art could not be loaded
+    pub(crate) images: Vec<Option<I>>,
+    pub(crate) battle: ReturnBattle,
+}
+
+struct Connection<T: Transport> {
+    transport: Rc<T>,
+    base_url: String,
+    headers: Vec<(&'static str, String)>,
+    retry_policy: RetryPolicy,
+    timeout: Duration,
+}
+impl<T: Transport> Connection<T> {
+    async fn send_once(
+        &self,
+        method: HttpMethod,
+        part: &str,
+        body: Option<serde_json::Value>,
+    ) -> ClientResult<(u16, Vec<u8>)> {
+        let request = async {
+            let res = self
+                .transport
+                .send(Request {
+                    method,
+                    url: join_url(&self.base_url, part),
+                    body,
+                    headers: self.headers.clone(),
+                })
+                .await
+                .map_err(ClientError::Transport)?;
+            Ok((res.status, res.body))
+        };
+        retry::with_timeout(self.timeout, request).await
+    }
+    async fn send(
+        &self,
+        method: HttpMethod,
+        part: &str,
+        body: Option<serde_json::Value>,
+    ) -> ClientResult<(u16, Vec<u8>)> {
+        let idempotent = method == HttpMethod::Get;
+        let mut attempt = 0;
+        loop {
+            let res = self.send_once(method, part, body.clone()).await;
+            let should_retry = match &res {
+                Err(ClientError::Transport(_)) | Err(ClientError::Timeout) => true,
+                Ok((status, _)) => *status >= 500,
+                Err(_) => false,
+            };
+            if !idempotent || !should_retry || attempt >= self.retry_policy.max_retries {
+                return res;
+            }
+            retry::Delay::new(self.retry_policy.delay_for(attempt)).await;
+            attempt += 1;
+        }
+    }
+    async fn fetch_asset(&self, path: &str) -> ClientResult<Vec<u8>> {
+        let (status, res) = self
+            .send(HttpMethod::Get, &(String::from("assets/") + path), None)
+            .await?;
+        if !is_success(status) {
+            return Err(ClientError::Status(status));
+        }
+        Ok(res)
+    }
+}
+
+pub(crate) struct Client<T: Transport = SessionTransport, I: TextureSize = Image> {
+    //shared with requests that outlive a borrow of the client, like `ping`
+    transport: Rc<T>,
+    pub base_url: String,
+    pub(crate) retry_policy: RetryPolicy,
+    ///How long a single attempt at a request may take
+    pub(crate) timeout: Duration,
+    authorization_code: Option<String>,
+    pub(crate) cached_images: ImageCache<I>,
+    ///Without one, assets are only kept in memory for as long as the game runs
+    pub(crate) asset_cache: Option<AssetCache>,
+    //as reported by the server, assets can only be taken from the asset cache once this is known
+    asset_version: Option<String>,
+    //images that failed to load, with when to try again and how often that already happened
+    failed_images: HashMap<String, (Instant, u32)>,
+    pub(crate) cards: CardRegistry,
+}
+impl<T: Transport, I: TextureSize> Client<T, I> {
+    pub(crate) fn with_transport(base_url: String, transport: T) -> Self {
+        Client {
+            transport: Rc::new(transport),
+            base_url,
+            retry_policy: RetryPolicy::default(),
+            timeout: Duration::from_secs(10),
+            authorization_code: None,
+            cached_images: ImageCache::new(ImageBudget::default()),
+            asset_cache: None,
+            asset_version: None,
+            failed_images: HashMap::new(),
+            cards: CardRegistry::default(),
+        }
+    }
+
+    //a snapshot of everything needed to make requests, for requests that outlive a borrow of the client
+    fn connection(&self) -> Connection<T> {
+        Connection {
+            transport: Rc::clone(&self.transport),
+            base_url: self.base_url.clone(),
+            headers: match &self.authorization_code {
+                Some(code) => vec![("authorization_token", code.clone())],
+                None => Vec::new(),
+            },
+            retry_policy: self.retry_policy.clone(),
+            timeout: self.timeout,
+        }
+    }
+    async fn send<B: Serialize>(
+        &self,
+        method: HttpMethod,
+        part: &str,
+        body: Option<B>,
+    ) -> ClientResult<(u16, Vec<u8>)> {
+        let body = body.map(serde_json::to_value).transpose()?;
+        self.connection().send(method, part, body).await
+    }
+    async fn request<B: Serialize, R: DeserializeOwned>(
+        &self,
+        method: HttpMethod,
+        part: &str,
+        body: Option<B>,
+    ) -> ClientResult<R> {
+        let (status, body) = self.send(method, part, body).await?;
+        decode(status, &body)
+    }
+    pub(crate) fn authorization_code(&self) -> Option<&str> {
+        self.authorization_code.as_deref()
+    }
+    pub(crate) fn clear_session(&mut self) {
+        self.authorization_code = None;
+    }
+    ///Switches to another server. The session and asset version of the old one mean nothing to it,
+    ///so they are forgotten until the new server tells us its own
+    pub(crate) fn set_base_url(&mut self, base_url: String) {
+        if base_url != self.base_url {
+            self.clear_session();
+            self.asset_version = None;
+            self.base_url = base_url;
+        }
+    }
+    pub(crate) async fn log_out(&mut self) -> ClientResult<()> {
+        let res = self.send::<()>(HttpMethod::Post, "logout", None).await;
+        self.clear_session();
+        let (status, body) = res?;
+        match status {
+            x if is_success(x) => Ok(()),
+            //not every server has a logout endpoint, in which case forgetting the token is all we can do
+            404 | 405 => Ok(()),
+            _ => Err(serde_json::from_slice::<ErrorRes>(&body)
+                .map(|v| ClientError::from_error_res(status, v))
+                .unwrap_or(ClientError::Status(status))),
+        }
+    }
+    ///Checks if a server is up, without having to switch to it first.
+    ///Any response counts, as even an error means there is something listening.
+    ///
+    ///The returned future doesn't borrow the client, so it can be polled a bit every frame rather than awaited.
+    pub(crate) fn ping(&self, base_url: &str) -> impl Future<Output = ClientResult<Duration>> + 'static
+    where
+        T: 'static,
+    {
+        let transport = Rc::clone(&self.transport);
+        let timeout = self.timeout;
+        let url = join_url(base_url, "version");
+        async move {
+            let start = Instant::now();
+            let request = async {
+                transport
+                    .probe(Request {
+                        method: HttpMethod::Get,
+                        url,
+                        body: None,
+                        headers: Vec::new(),
+                    })
+                    .await
+                    .map_err(ClientError::Transport)
+            };
+            retry::with_timeout(timeout, request).await?;
+            Ok(start.elapsed())
+        }
+    }
+    ///Makes sure the server speaks the same protocol as us, before we try to talk to it for real.
+    ///Servers from before the version endpoint existed can't tell us, so those are assumed to be compatible
+    pub(crate) async fn check_compatibility(&mut self) -> ClientResult<()> {
+        let res = self
+            .request::<(), VersionResponse>(HttpMethod::Get, "version", None)
+            .await;
+        let server = match res {
+            Ok(x) => x,
+            //servers from before /version existed answer with a plain or a json 404
+            Err(ClientError::Status(404)) | Err(ClientError::Server { status: 404, .. }) => {
+                self.asset_version = None;
+                return Ok(());
+            }
+            Err(x) => return Err(x),
+        };
+        self.asset_version = server.asset_version;
+        if server.protocol_version == PROTOCOL_VERSION {
+            Ok(())
+        } else {
+            Err(ClientError::Incompatible {
+                client: PROTOCOL_VERSION,
+                server: server.protocol_version,
+            })
+        }
+    }
+    ///Reuses a token from an earlier session, as long as the server still accepts it
+    pub(crate) async fn resume_session(&mut self, token: String) -> ClientResult<()> {
+        self.check_compatibility().await?;
+        self.authorization_code = Some(token);
+        if let Err(x) = self.get_characters().await {
+            self.authorization_code = None;
+            return Err(x);
+        }
+        Ok(())
+    }
+    pub(crate) async fn log_in(&mut self, username: String, password: String) -> ClientResult<()> {
+        let v: LoginResponse = self
+            .request(HttpMethod::Post, "login", Some(LoginData { username, password }))
+            .await?;
+        self.authorization_code = Some(v.token);
+        Ok(())
+    }
+    pub(crate) async fn register(&mut self, username: String, password: String) -> ClientResult<()> {
+        let v: LoginResponse = self
+            .request(HttpMethod::Post, "register", Some(LoginData { username, password }))
+            .await?;
+        self.authorization_code = Some(v.token);
+        Ok(())
+    }
+    async fn fetch_asset(&self, path: &str) -> ClientResult<Vec<u8>> {
+        self.connection().fetch_asset(path).await
+    }
+    fn cached_asset(&mut self, path: &str) -> Option<Vec<u8>> {
+        match (&mut self.asset_cache, &self.asset_version) {
+            (Some(cache), Some(version)) => cache.get(&self.base_url, path, version),
+            _ => None,
+        }
+    }
+    fn store_asset(&mut self, path: &str, bytes: &[u8]) {
+        if let (Some(cache), Some(version)) = (&mut self.asset_cache, &self.asset_version) {
+            //the cache already made as much room as it could, so this only fails for assets too big to store at all.
+            //Those just get downloaded again next time
+            let _ = cache.insert(&self.base_url, path, version, bytes);
+        }
+    }
+    //saves which cached assets got used, done once per batch rather than for every asset
+    fn save_asset_usage(&mut self) {
+        if let Some(cache) = &mut self.asset_cache {
+            //only used to pick what to evict, so losing this isn't worth failing over
+            let _ = cache.save_usage();
+        }
+    }
+    ///Gets every asset that isn't in memory yet, from the asset cache if possible.
+    ///The rest is downloaded all at once, so it takes as long as the slowest one rather than all of them combined.
+    ///Paths that are asked for multiple times only get downloaded once.
+    async fn fetch_missing_assets(&mut self, paths: &[String]) -> Vec<(String, ClientResult<Vec<u8>>)> {
+        let mut found = Vec::new();
+        let mut missing: Vec<&String> = Vec::new();
+        for path in paths {
+            if missing.contains(&path)
+                || found.iter().any(|(v, _)| v == path)
+                || self.cached_images.touch(path)
+            {
+                continue;
+            }
+            match self.cached_asset(path) {
+                Some(bytes) => found.push((path.clone(), Ok(bytes))),
+                None => missing.push(path),
+            }
+        }
+        self.save_asset_usage();
+        let fetched = join_all(missing.iter().map(|path| self.fetch_asset(path))).await;
+        for (path, res) in missing.into_iter().zip(fetched) {
+            if let Ok(bytes) = &res {
+                self.store_asset(path, bytes);
+            }
+            found.push((path.clone(), res));
+        }
+        found
+    }
+    ///Loads every image that isn't in memory yet.
+    ///Images that fail to load are remembered, so `retry_card_art` knows when to try them again.
+    async fn load_images(
+        &mut self,
+        paths: &[String],
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> Vec<(String, ClientError)> {
+        let fetched = self.fetch_missing_assets(paths).await;
+        self.add_images_from(fetched, gfx)
+    }
+    fn add_images_from(
+        &mut self,
+        fetched: Vec<(String, ClientResult<Vec<u8>>)>,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> Vec<(String, ClientError)> {
+        let mut failed = Vec::new();
+        for (path, res) in fetched {
+            let res = res.and_then(|bytes| {
+                gfx.decode(&bytes).map_err(|error| ClientError::Asset {
+                    path: path.clone(),
+                    error,
+                })
+            });
+            match res {
+                Ok(image) => {
+                    self.failed_images.remove(&path);
+                    self.cached_images.insert(path, image);
+                }
+                Err(error) => {
+                    if let Some(cache) = &mut self.asset_cache {
+                        let _ = cache.forget(&self.base_url, &path);
+                    }
+                    let attempts = self.failed_images.get(&path).map(|v| v.1 + 1).unwrap_or(0);
+                    self.failed_images
+                        .insert(path.clone(), (Instant::now() + art_retry_delay(attempts), attempts));
+                    failed.push((path, error));
+                }
+            }
+        }
+        failed
+    }
+    ///Starts downloading the art of cards that failed to load before, as long as it is time to try again.
+    ///The download doesn't borrow the client, so it can be polled with `poll_card_art` every frame rather than awaited
+    pub(crate) fn retry_card_art(&mut self, ids: &[String]) -> Option<PendingArt>
+    where
+        T: 'static,
+    {
+        let now = Instant::now();
+        let due: Vec<_> = ids
+            .iter()
+            .filter(|id| {
+                self.failed_images
+                    .get(&card_art_path(id))
+                    .map(|(retry_at, _)| *retry_at <= now)
+                    .unwrap_or(true)
+            })
+            .cloned()
+            .collect();
+        if due.is_empty() {
+            return None;
+        }
+        let paths: Vec<_> = due.iter().map(|id| card_art_path(id)).collect();
+        //pushed back while this attempt is running, so a slow download doesn't get started again every frame
+        for path in &paths {
+            let attempts = self.failed_images.get(path).map(|v| v.1).unwrap_or(0);
+            self.failed_images
+                .insert(path.clone(), (now + art_retry_delay(attempts), attempts));
+        }
+        let connection = self.connection();
+        let download = async move {
+            let fetched = join_all(paths.iter().map(|path| connection.fetch_asset(path))).await;
+            paths.into_iter().zip(fetched).collect()
+        };
+        Some(PendingArt {
+            ids: due,
+            download: Box::pin(download),
+        })
+    }
+    ///Finishes a `retry_card_art` once its download is done, returning `None` until then.
+    ///Returns the card ids whose art is available now, together with that art
+    pub(crate) fn poll_card_art(
+        &mut self,
+        pending: &mut PendingArt,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> Option<Vec<(String, I)>> {
+        let fetched = pending.download.as_mut().now_or_never()?;
+        for (path, res) in &fetched {
+            if let Ok(bytes) = res {
+                self.store_asset(path, bytes);
+            }
+        }
+        self.add_images_from(fetched, gfx);
+        Some(
+            pending
+                .ids
+                .iter()
+                .filter_map(|id| {
+                    self.cached_images
+                        .peek(&card_art_path(id))
+                        .map(|v| (id.clone(), v))
+                })
+                .collect(),
+        )
+    }
+    ///Gets the definitions of every card from the server, going through the asset cache like card art does.
+    ///Without them the registry stays empty, in which case cards are shown and played without their names and costs
+    pub(crate) async fn load_card_definitions(&mut self) -> ClientResult<()> {
+        self.cards = CardRegistry::default();
+        if let Some(bytes) = self.cached_asset(CARDS_FILE) {
+            match serde_json::from_slice::<CardFile>(&bytes) {
+                Ok(file) => {
+                    self.save_asset_usage();
+                    self.cards = file.into();
+                    return Ok(());
+                }
+                //a broken copy in the cache shouldn't stop us from getting a working one
+                Err(_) => {
+                    if let Some(cache) = &mut self.asset_cache {
+                        let _ = cache.forget(&self.base_url, CARDS_FILE);
+                    }
+                }
+            }
+        }
+        let bytes = self.fetch_asset(CARDS_FILE).await?;
+        let file: CardFile = serde_json::from_slice(&bytes)?;
+        self.store_asset(CARDS_FILE, &bytes);
+        self.cards = file.into();
+        Ok(())
+    }
+    ///The assets the server wants us to have loaded before the first battle
+    pub(crate) async fn asset_manifest(&self) -> ClientResult<AssetManifest> {
+        self.request::<(), _>(HttpMethod::Get, "assets/manifest.json", None)
+            .await
+    }
+    ///Starts loading an image into the cache ahead of time, so battles don't have to wait for it.
+    ///Returns `None` if it is already loaded, otherwise the load can be finished with `poll_preload` every frame
+    pub(crate) fn preload_image(&mut self, path: String) -> Option<PendingImage>
+    where
+        T: 'static,
+    {
+        if self.cached_images.touch(&path) {
+            return None;
+        }
+        //usage of the asset cache gets saved along with the next batch of lookups, not for every single file
+        let cached = self.cached_asset(&path);
+        let downloading = cached.is_none();
+        let load: Pin<Box<dyn Future<Output = ClientResult<Vec<u8>>>>> = match cached {
+            Some(bytes) => Box::pin(futures::future::ready(Ok(bytes))),
+            None => {
+                let connection = self.connection();
+                let asset = path.clone();
+                Box::pin(async move { connection.fetch_asset(&asset).await })
+            }
+        };
+        Some(PendingImage {
+            path,
+            downloading,
+            load,
+        })
+    }
+    ///Finishes a `preload_image` once the image is there, returning `None` until then
+    pub(crate) fn poll_preload(
+        &mut self,
+        pending: &mut PendingImage,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> Option<ClientResult<()>> {
+        let res = pending.load.as_mut().now_or_never()?;
+        if let (true, Ok(bytes)) = (pending.downloading, &res) {
+            self.store_asset(&pending.path, bytes);
+        }
+        Some(
+            match self.add_images_from(vec![(pending.path.clone(), res)], gfx).pop() {
+                Some((_, error)) => Err(error),
+                None => Ok(()),
+            },
+        )
+    }
+    ///Cards whose art fails to load get `None` rather than failing the whole battle, the art gets retried by `retry_card_art`
+    async fn add_images(
+        &mut self,
+        battle: ReturnBattle,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> ReturnBattleWithImages<I> {
+        let paths: Vec<_> = battle.hand.iter().map(|id| card_art_path(id)).collect();
+        //these are the cards that are about to be shown, so they should stay around
+        self.cached_images.pin(paths.iter().cloned());
+        self.load_images(&paths, gfx).await;
+        let cards = paths
+            .iter()
+            .map(|path| self.cached_images.peek(path))
+            .collect();
+        ReturnBattleWithImages {
+            battle,
+            images: cards,
+        }
+    }
+
+    async fn start_battle(&self, char_id: i64) -> ClientResult<ReturnBattle> {
+        self.request::<(), _>(HttpMethod::Post, &format!("battle/{}", char_id), None)
+            .await
+    }
+    pub(crate) async fn new_battle(
+        &mut self,
+        char_id: i64,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> ClientResult<ReturnBattleWithImages<I>> {
+        let res = self.start_battle(char_id).await?;
+        Ok(self.add_images(res, gfx).await)
+    }
+    async fn fetch_battle(&self, char_id: i64) -> ClientResult<ReturnBattle> {
+        self.request::<(), _>(HttpMethod::Get, &format!("battle/{}", char_id), None)
+            .await
+    }
+    pub(crate) async fn get_battle(
+        &mut self,
+        char_id: i64,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> ClientResult<ReturnBattleWithImages<I>> {
+        let res = self.fetch_battle(char_id).await?;
+        Ok(self.add_images(res, gfx).await)
+    }
+    pub(crate) async fn do_turn(
+        &mut self,
+        card: usize,
+        character_id: i64,
+        gfx: &impl ImageDecoder<Image = I>,
+    ) -> ClientResult<AfterTurn<ReturnBattleWithImages<I>>> {
+        Ok(match self.take_turn(card, character_id).await? {
+            AfterTurn::NewTurn(x) => AfterTurn::NewTurn(self.add_images(x, gfx).await),
+            AfterTurn::Over => AfterTurn::Over,
+            AfterTurn::NoTurnHappened => AfterTurn::NoTurnHappened,
+            AfterTurn::OutOfSync => AfterTurn::OutOfSync,
+        })
+    }
+    async fn take_turn(&self, card: usize, character_id: i64) -> ClientResult<AfterTurn<ReturnBattle>> {
+        let res: TurnResponse = self
+            .request(
+                HttpMethod::Put,
+                "battle/",
+                Some(TakeAction { play_card: card, character_id}),
+            )
+            .await?;
+        let res = match res {
+            TurnResponse::NextTurn(b) => b,
+            TurnResponse::Error(x) => match x {
+                BattleErrors::ChosenCardNotInHand(_) => {
+                    return Ok(AfterTurn::OutOfSync)
+                },
+                //what the card costs is taken from the card definitions instead, as those are known to the client
+                BattleErrors::CardCostsTooMuch { .. } => {
+                    return Ok(AfterTurn::NoTurnHappened)
+                }
+            },
+            //we should return something else to let the caller know the battle is over
+            //however, at this point the server doesn't even know when a battle is over (nor who won/lost)
+            //until that is added this should be decent enough.
+            TurnResponse::Done => return Ok(AfterTurn::Over),
+        };
+        Ok(AfterTurn::NewTurn(res))
+    }
+    pub(crate) async fn get_characters(&self) -> ClientResult<CharacterList> {
+        self.request::<(), _>(HttpMethod::Get, "characters", None).await
+    }
+    pub(crate) async fn create_character(&self) -> ClientResult<CharacterCreationResponse> {
+        self.request::<(), _>(HttpMethod::Post, "characters", None).await
+    }
+}
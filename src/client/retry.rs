@@ -0,0 +1,119 @@
+use super::{ClientError, ClientResult};
+use futures::future::{select, Either};
+use instant::Duration;
+use std::future::Future;
+#[cfg(target_arch = "wasm32")]
+use std::{
+    cell::RefCell,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+///How often and how fast requests get retried.
+///Only idempotent requests are ever retried, as retrying something like playing a card could play it twice.
+#[derive(Clone, Debug)]
+pub(crate) struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    ///The delay before the first retry, it doubles with every retry after that
+    pub(crate) base_delay: Duration,
+    pub(crate) max_delay: Duration,
+    ///How much of the delay may be randomly taken off, from 0.0 to 1.0.
+    ///This stops clients that failed together from all retrying at the exact same moment
+    pub(crate) jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(4),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub(crate) fn delay_for(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        delay.mul_f64(1. - self.jitter.clamp(0., 1.) * random())
+    }
+}
+
+//the browser's own random numbers, std has no source of randomness there
+#[cfg(target_arch = "wasm32")]
+fn random() -> f64 {
+    use stdweb::{js, unstable::TryInto};
+    js!(return Math.random();).try_into().unwrap_or(0.5)
+}
+
+//every `RandomState` gets its own keys, which std seeds from the OS once per thread.
+//So the hash of the current time differs between clients even if they all fail at the same moment
+#[cfg(not(target_arch = "wasm32"))]
+fn random() -> f64 {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+    };
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(instant::now().to_bits());
+    (hasher.finish() % 10_000) as f64 / 10_000.
+}
+
+///A future that finishes once the given time has passed, backed by a timer thread
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use futures_timer::Delay;
+
+///A future that finishes once the given time has passed, backed by `setTimeout`
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct Delay {
+    state: Rc<RefCell<(bool, Option<Waker>)>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Delay {
+    pub(crate) fn new(duration: Duration) -> Self {
+        let state = Rc::new(RefCell::new((false, None::<Waker>)));
+        let timer_state = Rc::clone(&state);
+        stdweb::web::set_timeout(
+            move || {
+                let mut state = timer_state.borrow_mut();
+                state.0 = true;
+                if let Some(waker) = state.1.take() {
+                    waker.wake();
+                }
+            },
+            duration.as_millis().min(u32::MAX as u128) as u32,
+        );
+        Self { state }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Future for Delay {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.borrow_mut();
+        if state.0 {
+            Poll::Ready(())
+        } else {
+            state.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+pub(crate) async fn with_timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = ClientResult<T>>,
+) -> ClientResult<T> {
+    match select(Box::pin(future), Delay::new(timeout)).await {
+        Either::Left((x, _)) => x,
+        Either::Right(_) => Err(ClientError::Timeout),
+    }
+}
@@ -0,0 +1,518 @@
+//! Drives the client from logging in until the battle is over, using a `MemoryTransport` in place of a server.
+//!
+//! The screens themselves need a window, so the way from one screen to the next is tested through `flow` instead,
+//! which is what the screens use to decide where to go. Images get decoded by a `FakeDecoder` for the same reason.
+use super::{
+    cassette::Replayer, AfterTurn, Client, ClientError, FakeDecoder, FakeImage, HttpMethod,
+    MemoryTransport, TextureSize, PROTOCOL_VERSION,
+};
+use crate::flow::{self, Played, Step};
+use futures::executor::block_on;
+use instant::Duration;
+use serde_json::{json, Value};
+
+const BASE: &str = "http://test";
+
+fn url(part: &str) -> String {
+    format!("{}/{}", BASE, part)
+}
+
+fn client() -> Client<MemoryTransport> {
+    let mut client = Client::with_transport(BASE.into(), MemoryTransport::new());
+    client.retry_policy.base_delay = Duration::from_millis(0);
+    client
+}
+
+//for tests that load images
+fn client_with_images() -> Client<MemoryTransport, FakeImage> {
+    let mut client = Client::with_transport(BASE.into(), MemoryTransport::new());
+    client.retry_policy.base_delay = Duration::from_millis(0);
+    client
+}
+
+fn battle(enemy_hp: i64) -> Value {
+    json!({
+        "hand": ["fireball", "heal"],
+        "small_runes": [],
+        "enemy_small_runes": [],
+        "hexa_runes": [],
+        "player_hp": 30,
+        "enemy_hp": enemy_hp,
+        "mana": 3,
+        "enemy_mana": 3,
+        "enemy_hand_size": 5,
+    })
+}
+
+fn log_in<I: TextureSize>(client: &mut Client<MemoryTransport, I>) {
+    client.transport.respond_json(
+        HttpMethod::Post,
+        &url("login"),
+        200,
+        &json!({"success": true, "token": "token"}),
+    );
+    block_on(client.log_in("user".into(), "password".into())).unwrap();
+}
+
+#[test]
+fn client_flow_from_login_to_battle_over() {
+    let mut client = client();
+    log_in(&mut client);
+    assert_eq!(client.authorization_code(), Some("token"));
+
+    client
+        .transport
+        .respond_json(HttpMethod::Get, &url("characters"), 200, &json!({"characters": []}));
+    client
+        .transport
+        .respond_json(HttpMethod::Post, &url("characters"), 200, &json!({"id": 7}));
+    assert!(block_on(client.get_characters()).unwrap().characters.is_empty());
+    let id = block_on(client.create_character()).unwrap().id;
+    assert_eq!(id, 7);
+
+    client
+        .transport
+        .respond_json(HttpMethod::Post, &url("battle/7"), 200, &battle(10));
+    let started = block_on(client.start_battle(id)).unwrap();
+    assert_eq!(started.hand, vec!["fireball", "heal"]);
+
+    client.transport.respond_json(
+        HttpMethod::Put,
+        &url("battle/"),
+        200,
+        &json!({ "NextTurn": battle(5) }),
+    );
+    client
+        .transport
+        .respond_json(HttpMethod::Put, &url("battle/"), 200, &json!("Done"));
+    match block_on(client.take_turn(0, id)).unwrap() {
+        AfterTurn::NewTurn(x) => assert_eq!(x.enemy_hp, 5),
+        _ => panic!("Expected a new turn"),
+    }
+    assert!(matches!(
+        block_on(client.take_turn(0, id)).unwrap(),
+        AfterTurn::Over
+    ));
+
+    let requests = client.transport.requests();
+    let sent: Vec<_> = requests
+        .iter()
+        .map(|v| (v.method, v.url.trim_start_matches(BASE)))
+        .collect();
+    assert_eq!(
+        sent,
+        vec![
+            (HttpMethod::Post, "/login"),
+            (HttpMethod::Get, "/characters"),
+            (HttpMethod::Post, "/characters"),
+            (HttpMethod::Post, "/battle/7"),
+            (HttpMethod::Put, "/battle/"),
+            (HttpMethod::Put, "/battle/"),
+        ]
+    );
+    assert!(requests[0].headers.is_empty());
+    assert!(requests[1..]
+        .iter()
+        .all(|v| v.headers == vec![("authorization_token", "token".to_owned())]));
+    assert_eq!(
+        requests[4].body,
+        Some(json!({"play_card": 0, "character_id": 7}))
+    );
+}
+
+#[test]
+fn flow_from_login_to_battle_over() {
+    let mut client = client_with_images();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("version"),
+        200,
+        &json!({ "protocol_version": PROTOCOL_VERSION }),
+    );
+    client.transport.respond_json(
+        HttpMethod::Post,
+        &url("login"),
+        200,
+        &json!({"success": true, "token": "token"}),
+    );
+    let next = block_on(flow::log_in(&mut client, "user".into(), "password".into())).unwrap();
+    assert!(matches!(next, Step::Preload));
+    assert_eq!(client.authorization_code(), Some("token"));
+
+    client
+        .transport
+        .respond_json(HttpMethod::Get, &url("characters"), 200, &json!({"characters": []}));
+    let next = block_on(flow::after_preload(&client)).unwrap();
+    assert!(matches!(next, Step::CharacterSelect { character: None }));
+
+    client
+        .transport
+        .respond_json(HttpMethod::Post, &url("characters"), 200, &json!({"id": 7}));
+    client
+        .transport
+        .respond_json(HttpMethod::Post, &url("battle/7"), 200, &battle(10));
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/fireball.png"), 200, vec![0; 3]);
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/heal.png"), 404, Vec::new());
+    let started = match block_on(flow::pick_character(&mut client, None, &FakeDecoder)).unwrap() {
+        Step::Battle {
+            character_id: 7,
+            battle,
+        } => battle,
+        _ => panic!("Expected a battle with the new character"),
+    };
+    assert_eq!(started.battle.hand, vec!["fireball", "heal"]);
+    //art that fails to load leaves a gap, rather than failing the whole battle
+    assert_eq!(started.images, vec![Some(FakeImage(3)), None]);
+
+    client.transport.respond_json(
+        HttpMethod::Put,
+        &url("battle/"),
+        200,
+        &json!({ "NextTurn": battle(5) }),
+    );
+    client
+        .transport
+        .respond_json(HttpMethod::Put, &url("battle/"), 200, &json!("Done"));
+    match block_on(flow::play_card(&mut client, 7, 0, &FakeDecoder)).unwrap() {
+        Played::Turn(x) => {
+            assert_eq!(x.battle.enemy_hp, 5);
+            assert_eq!(x.images[0], Some(FakeImage(3)));
+        }
+        _ => panic!("Expected a new turn"),
+    }
+    assert!(matches!(
+        block_on(flow::play_card(&mut client, 7, 0, &FakeDecoder)).unwrap(),
+        Played::Over
+    ));
+    //the art was already loaded for the first turn, so only the broken one got asked for again
+    let art_requests = client
+        .transport
+        .requests()
+        .iter()
+        .filter(|v| v.url.ends_with(".png"))
+        .count();
+    assert_eq!(art_requests, 3);
+}
+
+#[test]
+fn existing_character_goes_straight_into_battle() {
+    let mut client = client_with_images();
+    log_in(&mut client);
+    client
+        .transport
+        .respond_json(HttpMethod::Get, &url("characters"), 200, &json!({"characters": [4]}));
+    let character = match block_on(flow::after_preload(&client)).unwrap() {
+        Step::CharacterSelect { character } => character,
+        _ => panic!("Expected the character select"),
+    };
+    assert_eq!(character, Some(4));
+
+    client
+        .transport
+        .respond_json(HttpMethod::Post, &url("battle/4"), 200, &battle(10));
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/fireball.png"), 200, vec![0]);
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/heal.png"), 200, vec![0]);
+    assert!(matches!(
+        block_on(flow::pick_character(&mut client, character, &FakeDecoder)).unwrap(),
+        Step::Battle { character_id: 4, .. }
+    ));
+    assert!(!client
+        .transport
+        .requests()
+        .iter()
+        .any(|v| v.method == HttpMethod::Post && v.url == url("characters")));
+}
+
+#[test]
+fn out_of_sync_battle_gets_fetched_again() {
+    let mut client = client_with_images();
+    log_in(&mut client);
+    client.transport.respond_json(
+        HttpMethod::Put,
+        &url("battle/"),
+        200,
+        &json!({ "Error": { "ChosenCardNotInHand": 3 } }),
+    );
+    client
+        .transport
+        .respond_json(HttpMethod::Get, &url("battle/7"), 200, &battle(8));
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/fireball.png"), 200, vec![0]);
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/heal.png"), 200, vec![0]);
+    match block_on(flow::play_card(&mut client, 7, 3, &FakeDecoder)).unwrap() {
+        Played::Turn(x) => assert_eq!(x.battle.enemy_hp, 8),
+        _ => panic!("Expected the battle as the server sees it"),
+    }
+}
+
+#[test]
+fn card_not_in_hand_means_out_of_sync() {
+    let mut client = client();
+    log_in(&mut client);
+    client.transport.respond_json(
+        HttpMethod::Put,
+        &url("battle/"),
+        200,
+        &json!({ "Error": { "ChosenCardNotInHand": 3 } }),
+    );
+    assert!(matches!(
+        block_on(client.take_turn(3, 1)).unwrap(),
+        AfterTurn::OutOfSync
+    ));
+}
+
+#[test]
+fn server_errors_keep_their_message_and_code() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Post,
+        &url("login"),
+        401,
+        &json!({"code": "invalid_credentials", "message": "Wrong password"}),
+    );
+    match block_on(client.log_in("user".into(), "wrong".into())) {
+        Err(ClientError::Server { code, message, .. }) => {
+            assert_eq!(code.as_deref(), Some("invalid_credentials"));
+            assert_eq!(message, "Wrong password");
+        }
+        x => panic!("Expected a server error, got {:?}", x.err()),
+    }
+    assert_eq!(client.authorization_code(), None);
+}
+
+#[test]
+fn rejected_token_is_a_session_expiry() {
+    let mut client = client();
+    log_in(&mut client);
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("characters"), 401, Vec::new());
+    let error = block_on(client.get_characters()).unwrap_err();
+    assert!(error.is_session_expired());
+}
+
+#[test]
+fn rejected_token_with_an_explanation_is_a_session_expiry() {
+    let mut client = client();
+    log_in(&mut client);
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("characters"),
+        401,
+        &json!({"message": "Token no longer valid"}),
+    );
+    let error = block_on(client.get_characters()).unwrap_err();
+    assert!(matches!(error, ClientError::Server { status: 401, .. }));
+    assert!(error.is_session_expired());
+}
+
+#[test]
+fn only_idempotent_requests_get_retried() {
+    let client = client();
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("characters"), 503, Vec::new());
+    client
+        .transport
+        .respond_json(HttpMethod::Get, &url("characters"), 200, &json!({"characters": [1]}));
+    client
+        .transport
+        .respond(HttpMethod::Post, &url("characters"), 503, Vec::new());
+
+    assert_eq!(block_on(client.get_characters()).unwrap().characters, vec![1]);
+    assert!(matches!(
+        block_on(client.create_character()),
+        Err(ClientError::Status(503))
+    ));
+    let posts = client
+        .transport
+        .requests()
+        .iter()
+        .filter(|v| v.method == HttpMethod::Post)
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[test]
+fn log_out_forgets_the_token_without_a_logout_endpoint() {
+    let mut client = client();
+    log_in(&mut client);
+    client
+        .transport
+        .respond(HttpMethod::Post, &url("logout"), 404, Vec::new());
+    block_on(client.log_out()).unwrap();
+    assert_eq!(client.authorization_code(), None);
+}
+
+#[test]
+fn mismatched_protocol_is_incompatible() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("version"),
+        200,
+        &json!({"protocol_version": PROTOCOL_VERSION + 1}),
+    );
+    match block_on(client.check_compatibility()) {
+        Err(ClientError::Incompatible { client, server }) => {
+            assert_eq!(client, PROTOCOL_VERSION);
+            assert_eq!(server, PROTOCOL_VERSION + 1);
+        }
+        x => panic!("Expected an incompatible server, got {:?}", x.err()),
+    }
+}
+
+#[test]
+fn server_without_a_version_endpoint_is_compatible() {
+    let mut client = client();
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("version"), 404, Vec::new());
+    block_on(client.check_compatibility()).unwrap();
+}
+
+#[test]
+fn server_with_a_json_404_for_version_is_compatible() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("version"),
+        404,
+        &json!({ "message": "Unknown endpoint" }),
+    );
+    block_on(client.check_compatibility()).unwrap();
+    assert_eq!(client.asset_version, None);
+}
+
+#[test]
+fn switching_servers_forgets_the_asset_version() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("version"),
+        200,
+        &json!({ "protocol_version": PROTOCOL_VERSION, "asset_version": "3" }),
+    );
+    block_on(client.check_compatibility()).unwrap();
+    assert_eq!(client.asset_version.as_deref(), Some("3"));
+    client.set_base_url(BASE.into());
+    assert_eq!(client.asset_version.as_deref(), Some("3"));
+    client.set_base_url("http://other".into());
+    assert_eq!(client.asset_version, None);
+}
+
+#[test]
+fn card_definitions_come_from_the_server() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("assets/cards.json"),
+        200,
+        &json!({"cards": [{"id": "heal", "name": "Heal", "cost": 1}]}),
+    );
+    block_on(client.load_card_definitions()).unwrap();
+    assert_eq!(client.cards.get("heal").unwrap().cost, 1);
+}
+
+#[test]
+fn without_card_definitions_the_registry_stays_empty() {
+    let mut client = client();
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards.json"), 404, Vec::new());
+    assert!(block_on(client.load_card_definitions()).is_err());
+    assert!(client.cards.get("heal").is_none());
+}
+
+#[test]
+fn ping_can_outlive_the_client() {
+    let client = client();
+    client
+        .transport
+        .respond(HttpMethod::Get, "http://other/version", 404, Vec::new());
+    let ping = client.ping("http://other");
+    drop(client);
+    assert!(block_on(ping).is_ok());
+}
+
+#[test]
+fn refused_session_is_not_resumed() {
+    let mut client = client();
+    client.transport.respond_json(
+        HttpMethod::Get,
+        &url("version"),
+        200,
+        &json!({ "protocol_version": PROTOCOL_VERSION }),
+    );
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("characters"), 401, Vec::new());
+    assert!(block_on(client.resume_session("old".into())).is_err());
+    assert_eq!(client.authorization_code(), None);
+}
+
+#[test]
+fn cassette_replays_responses_in_recorded_order() {
+    let cassette = serde_json::from_value(json!({
+        "interactions": [
+            {"method": "Post", "path": "login", "request": null, "status": 200,
+                "response": {"Json": {"success": true, "token": "token"}}},
+            {"method": "Put", "path": "battle/", "request": null, "status": 200,
+                "response": {"Json": {"NextTurn": battle(5)}}},
+            {"method": "Put", "path": "battle/", "request": null, "status": 200,
+                "response": {"Json": "Done"}},
+        ]
+    }))
+    .unwrap();
+    //recorded against another server, only the paths need to match
+    let mut client = Client::with_transport(BASE.into(), Replayer::new(cassette));
+    block_on(client.log_in("user".into(), "password".into())).unwrap();
+    assert_eq!(client.authorization_code(), Some("token"));
+    assert!(matches!(
+        block_on(client.take_turn(0, 1)).unwrap(),
+        AfterTurn::NewTurn(_)
+    ));
+    assert!(matches!(
+        block_on(client.take_turn(0, 1)).unwrap(),
+        AfterTurn::Over
+    ));
+    assert!(block_on(client.take_turn(0, 1)).is_err());
+}
+
+#[test]
+fn identical_cards_are_only_downloaded_once() {
+    let mut client = client();
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/heal.png"), 200, vec![1]);
+    client
+        .transport
+        .respond(HttpMethod::Get, &url("assets/cards/fireball.png"), 200, vec![2]);
+    let paths: Vec<String> = ["cards/heal.png", "cards/fireball.png", "cards/heal.png"]
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    let fetched: Vec<_> = block_on(client.fetch_missing_assets(&paths))
+        .into_iter()
+        .map(|(path, res)| (path, res.unwrap()))
+        .collect();
+    assert_eq!(
+        fetched,
+        vec![
+            ("cards/heal.png".to_owned(), vec![1]),
+            ("cards/fireball.png".to_owned(), vec![2]),
+        ]
+    );
+    assert_eq!(client.transport.requests().len(), 2);
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use silver_surf::{call, Config, Method};
+#[cfg(test)]
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub(crate) enum HttpMethod {
+    Get,
+    Post,
+    Put,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+    pub(crate) method: HttpMethod,
+    pub(crate) url: String,
+    pub(crate) body: Option<serde_json::Value>,
+    pub(crate) headers: Vec<(&'static str, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) body: Vec<u8>,
+}
+
+///Whatever gets the requests of the `Client` to a server and back.
+///Errors are only for when there is no response at all, an error status is still a response.
+#[async_trait(?Send)]
+pub(crate) trait Transport {
+    async fn send(&self, request: Request) -> crate::Result<Response>;
+    ///Sends a request that only checks if the server is there, like the ping of the server status.
+    ///Those aren't part of the session, so transports keeping track of the session leave them out
+    async fn probe(&self, request: Request) -> crate::Result<Response> {
+        self.send(request).await
+    }
+}
+
+///Sends requests over the network using silver_surf
+#[derive(Default)]
+pub(crate) struct SilverSurf;
+
+#[async_trait(?Send)]
+impl Transport for SilverSurf {
+    async fn send(&self, request: Request) -> crate::Result<Response> {
+        let method = match request.method {
+            HttpMethod::Get => Method::Get,
+            HttpMethod::Post => Method::Post,
+            HttpMethod::Put => Method::Put,
+        };
+        let headers = if request.headers.is_empty() {
+            None
+        } else {
+            Some(request.headers)
+        };
+        let res = call(Config {
+            url: request.url,
+            method,
+            body: request.body,
+            headers,
+        })?;
+        let status: u16 = res.status().into();
+        let body = res.bytes().await?;
+        Ok(Response { status, body })
+    }
+}
+
+///Answers requests with responses set up beforehand, so the `Client` can be used without a server.
+///
+///Responses for the same request are given out in order, with the last one being repeated after that.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryTransport {
+    responses: RefCell<HashMap<(HttpMethod, String), VecDeque<Response>>>,
+    requests: RefCell<Vec<Request>>,
+}
+
+#[cfg(test)]
+impl MemoryTransport {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+    pub(crate) fn respond(&self, method: HttpMethod, url: &str, status: u16, body: Vec<u8>) {
+        self.responses
+            .borrow_mut()
+            .entry((method, url.to_owned()))
+            .or_default()
+            .push_back(Response { status, body });
+    }
+    pub(crate) fn respond_json<T: serde::Serialize>(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        status: u16,
+        body: &T,
+    ) {
+        let body = serde_json::to_vec(body).expect("Could not serialize the canned response");
+        self.respond(method, url, status, body)
+    }
+    ///Every request that got sent so far, oldest first
+    pub(crate) fn requests(&self) -> Vec<Request> {
+        self.requests.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+#[async_trait(?Send)]
+impl Transport for MemoryTransport {
+    async fn send(&self, request: Request) -> crate::Result<Response> {
+        let key = (request.method, request.url.clone());
+        self.requests.borrow_mut().push(request);
+        let mut responses = self.responses.borrow_mut();
+        let queue = responses
+            .get_mut(&key)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| format!("No response set up for {:?} {}", key.0, key.1))?;
+        Ok(if queue.len() > 1 {
+            queue.pop_front().unwrap()
+        } else {
+            queue[0].clone()
+        })
+    }
+}
@@ -0,0 +1,97 @@
+//! Which screen comes next, apart from everything needed to draw it.
+//!
+//! The screens ask this what happens after a button gets clicked and then show whatever `Step` they get back,
+//! see `screens::show`. Nothing here needs a window, so the way from logging in until the battle is over can be tested.
+use crate::client::{
+    AfterTurn, Client, ClientResult, ImageDecoder, ReturnBattleWithImages, TextureSize, Transport,
+};
+use quicksilver::graphics::Image;
+
+///A screen of the game, together with what it got from the server
+///
+///Logging out isn't a step, as going back to the login screen is the same from anywhere.
+pub(crate) enum Step<I = Image> {
+    Preload,
+    CharacterSelect {
+        ///`None` if the player doesn't have a character yet
+        character: Option<i64>,
+    },
+    Battle {
+        character_id: i64,
+        battle: ReturnBattleWithImages<I>,
+    },
+    BattleOver,
+}
+
+///What playing a card in a battle led to
+pub(crate) enum Played<I = Image> {
+    ///The battle goes on from here
+    Turn(ReturnBattleWithImages<I>),
+    ///The server refused the card, as it costs more mana than the player has
+    Refused,
+    ///Time for `Step::BattleOver`
+    Over,
+}
+
+pub(crate) async fn log_in<T: Transport, I: TextureSize>(
+    client: &mut Client<T, I>,
+    username: String,
+    password: String,
+) -> ClientResult<Step<I>> {
+    client.check_compatibility().await?;
+    client.log_in(username, password).await?;
+    Ok(Step::Preload)
+}
+
+pub(crate) async fn register<T: Transport, I: TextureSize>(
+    client: &mut Client<T, I>,
+    username: String,
+    password: String,
+) -> ClientResult<Step<I>> {
+    client.check_compatibility().await?;
+    client.register(username, password).await?;
+    Ok(Step::Preload)
+}
+
+///Once the card art is preloaded, or the player got tired of waiting for it
+pub(crate) async fn after_preload<T: Transport, I: TextureSize>(
+    client: &Client<T, I>,
+) -> ClientResult<Step<I>> {
+    let characters = client.get_characters().await?;
+    Ok(Step::CharacterSelect {
+        character: characters.characters.get(0).copied(),
+    })
+}
+
+///Starts a battle with the given character, making a new one first if there is none
+pub(crate) async fn pick_character<T: Transport, D: ImageDecoder>(
+    client: &mut Client<T, D::Image>,
+    character: Option<i64>,
+    gfx: &D,
+) -> ClientResult<Step<D::Image>> {
+    let character_id = match character {
+        Some(x) => x,
+        None => client.create_character().await?.id,
+    };
+    let battle = client.new_battle(character_id, gfx).await?;
+    Ok(Step::Battle {
+        character_id,
+        battle,
+    })
+}
+
+pub(crate) async fn play_card<T: Transport, D: ImageDecoder>(
+    client: &mut Client<T, D::Image>,
+    character_id: i64,
+    card: usize,
+    gfx: &D,
+) -> ClientResult<Played<D::Image>> {
+    Ok(match client.do_turn(card, character_id, gfx).await? {
+        AfterTurn::NewTurn(x) => Played::Turn(x),
+        //only happens when we didn't know the cost of the card, otherwise the hand wouldn't have sent it
+        AfterTurn::NoTurnHappened => Played::Refused,
+        //our view of the battle no longer matches the server, so throw it away and get a fresh one
+        AfterTurn::OutOfSync => Played::Turn(client.get_battle(character_id, gfx).await?),
+        AfterTurn::Over => Played::Over,
+    })
+}
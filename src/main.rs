@@ -1,13 +1,12 @@
 use crate::screens::screen::Screen;
+use client::{AssetCache, ClientError, SessionTransport};
 pub(crate) use client::Client;
 use quicksilver::input::Event::{PointerMoved};
 use quicksilver::{
     geom::{Vector},
     graphics::{Graphics, Image, ResizeHandler, VectorFont},
     input::Input,
-    load_file, run,
-    saving::Location,
-    Settings, Window,
+    run, Settings, Window,
 };
 use std::error::Error as TError;
 
@@ -15,7 +14,9 @@ use mergui::{Context};
 
 mod animations;
 mod client;
+mod flow;
 mod responses;
+mod saved;
 mod screen_parts;
 mod screens;
 
@@ -42,6 +43,7 @@ pub(crate) struct Wrapper {
     pub events: Input,
     pub context: Context,
     pub client: Client,
+    pub(crate) profiles: saved::ServerProfiles,
     pub font: VectorFont,
     pub button_image: Image,
     cursor_at: Vector,
@@ -52,17 +54,28 @@ impl Wrapper {
     }
 }
 
+//an expired session is the only error we can recover from here, by sending the user back to the login screen
+async fn recover(wrapper: &mut Wrapper, error: Error) -> Result<Box<dyn Screen>> {
+    match error.downcast_ref::<ClientError>() {
+        Some(x) if x.is_session_expired() => {
+            saved::forget_token(&wrapper.client.base_url)?;
+            wrapper.client.clear_session();
+            let login = screens::Login::with_error(
+                wrapper,
+                "Your session expired, please log in again.".into(),
+            )
+            .await?;
+            Ok(Box::new(login))
+        }
+        _ => Err(error),
+    }
+}
+
 async fn app(window: Window, gfx: Graphics, events: Input) -> Result<()> {
     let context = Context::new();
 
-    let last_used_url = match quicksilver::saving::load::<String>(
-        Location::Config,
-        APP_NAME,
-        "last_connected_server",
-    ) {
-        Ok(x) => x,
-        Err(_) => String::from_utf8(load_file("default_server.txt").await?).unwrap(),
-    };
+    let profiles = saved::ServerProfiles::load().await?;
+    let transport = SessionTransport::load().await?;
     let font = VectorFont::load("font.ttf").await?;
     let button_image = Image::load(&gfx, "./button.png").await?;
     let mut wrapper = Wrapper {
@@ -70,12 +83,28 @@ async fn app(window: Window, gfx: Graphics, events: Input) -> Result<()> {
         gfx,
         events,
         context,
-        client: Client::new(last_used_url),
+        client: Client::with_transport(profiles.selected().url.clone(), transport),
+        profiles,
         cursor_at: Vector::new(0., 0.),
         font,
         button_image,
     };
-    let mut v: Box<dyn Screen> = Box::new(screens::Login::new(&mut wrapper).await?);
+    wrapper.client.asset_cache = Some(AssetCache::load());
+    let mut v: Box<dyn Screen> = match saved::load_token(&wrapper.client.base_url) {
+        Some(token) => match wrapper.client.resume_session(token).await {
+            Ok(()) => Box::new(screens::Preload::new(&mut wrapper).await?),
+            Err(x) if x.is_session_expired() => {
+                saved::forget_token(&wrapper.client.base_url)?;
+                Box::new(screens::Login::new(&mut wrapper).await?)
+            }
+            Err(x @ ClientError::Incompatible { .. }) => Box::new(
+                screens::Login::with_error(&mut wrapper, screens::describe_error(&x)).await?,
+            ),
+            //the server might just be down or misbehaving for a moment, so the token is kept for next time
+            Err(_) => Box::new(screens::Login::new(&mut wrapper).await?),
+        },
+        None => Box::new(screens::Login::new(&mut wrapper).await?),
+    };
     v.draw(&mut wrapper).await?;
 
     // Create a ResizeHandler that will Fit the content to the screen, leaving off area if we need
@@ -91,14 +120,20 @@ async fn app(window: Window, gfx: Graphics, events: Input) -> Result<()> {
                 wrapper.cursor_at = e.location();
             }
             wrapper.context.event(&e, &wrapper.window);
-            if let Some(x) = v.event(&mut wrapper, &e).await? {
-                v = x;
+            match v.event(&mut wrapper, &e).await {
+                Ok(Some(x)) => v = x,
+                Ok(None) => {}
+                Err(x) => v = recover(&mut wrapper, x).await?,
             }
         }
-        if let Some(x) = v.update(&mut wrapper).await? {
-            v = x;
+        match v.update(&mut wrapper).await {
+            Ok(Some(x)) => v = x,
+            Ok(None) => {}
+            Err(x) => v = recover(&mut wrapper, x).await?,
+        }
+        if let Err(x) = v.draw(&mut wrapper).await {
+            v = recover(&mut wrapper, x).await?;
         }
-        v.draw(&mut wrapper).await?;
         wrapper.context.render(&mut wrapper.gfx, &wrapper.window)?;
         wrapper.gfx.present(&wrapper.window)?;
     }
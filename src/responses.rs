@@ -1,15 +1,19 @@
+use crate::client::{ClientError, ClientResult};
 use serde::Deserialize;
 use std::{error::Error, fmt};
 
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
 pub(crate) enum ErrorRes {
+    WithCode { code: String, message: String },
     Basic { message: String },
 }
 impl fmt::Display for ErrorRes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ErrorRes::Basic { message } => write!(f, "{}", message),
+            ErrorRes::WithCode { message, .. } | ErrorRes::Basic { message } => {
+                write!(f, "{}", message)
+            }
         }
     }
 }
@@ -21,6 +25,18 @@ pub(crate) struct LoginResponse {
     pub(crate) token: String,
 }
 #[derive(Deserialize, Debug)]
+pub(crate) struct VersionResponse {
+    pub(crate) protocol_version: u32,
+    ///Changes whenever the assets on the server do, older servers don't send it
+    #[serde(default)]
+    pub(crate) asset_version: Option<String>,
+}
+#[derive(Deserialize, Debug)]
+pub(crate) struct AssetManifest {
+    ///Paths relative to the assets folder, like `cards/fireball.png`
+    pub(crate) files: Vec<String>,
+}
+#[derive(Deserialize, Debug)]
 #[serde(untagged)]
 pub(crate) enum CustomResult<T> {
     Ok(T),
@@ -28,8 +44,10 @@ pub(crate) enum CustomResult<T> {
 }
 
 impl<T> CustomResult<T> {
-    pub(crate) fn into_dyn_res(self) -> Result<T, crate::Error> {
-        Ok(std::result::Result::<_, ErrorRes>::from(self)?)
+    ///`status` is the status code the response came with, which errors keep around
+    pub(crate) fn into_client_res(self, status: u16) -> ClientResult<T> {
+        std::result::Result::<_, ErrorRes>::from(self)
+            .map_err(|v| ClientError::from_error_res(status, v))
     }
 }
 
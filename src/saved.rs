@@ -0,0 +1,107 @@
+use crate::{Result, APP_NAME};
+use quicksilver::{
+    load_file,
+    saving::{load, save, Location},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const TOKENS: &str = "tokens";
+
+//tokens are stored per server, so switching servers doesn't log us out of the others
+fn load_tokens() -> HashMap<String, String> {
+    load(Location::Config, APP_NAME, TOKENS).unwrap_or_default()
+}
+
+pub(crate) fn load_token(server: &str) -> Option<String> {
+    load_tokens().remove(server)
+}
+
+pub(crate) fn save_token(server: &str, token: String) -> Result<()> {
+    let mut tokens = load_tokens();
+    tokens.insert(server.to_owned(), token);
+    save(Location::Config, APP_NAME, TOKENS, &tokens)?;
+    Ok(())
+}
+
+pub(crate) fn forget_token(server: &str) -> Result<()> {
+    let mut tokens = load_tokens();
+    if tokens.remove(server).is_some() {
+        save(Location::Config, APP_NAME, TOKENS, &tokens)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct ServerProfile {
+    pub(crate) name: String,
+    pub(crate) url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ServerProfiles {
+    profiles: Vec<ServerProfile>,
+    selected: usize,
+}
+
+const SERVER_PROFILES: &str = "server_profiles";
+
+impl ServerProfiles {
+    pub(crate) async fn load() -> Result<Self> {
+        if let Ok(mut profiles) = load::<Self>(Location::Config, APP_NAME, SERVER_PROFILES) {
+            if !profiles.profiles.is_empty() {
+                //the file may have been edited by hand, so the selected profile might not exist
+                profiles.selected = profiles.selected.min(profiles.profiles.len() - 1);
+                return Ok(profiles);
+            }
+        }
+        //older versions only remembered a single server, so start from that one if we can
+        let url = match load::<String>(Location::Config, APP_NAME, "last_connected_server") {
+            Ok(x) => x,
+            Err(_) => String::from_utf8(load_file("default_server.txt").await?)?
+                .trim()
+                .to_owned(),
+        };
+        Ok(Self {
+            profiles: vec![ServerProfile {
+                name: "Default".into(),
+                url,
+            }],
+            selected: 0,
+        })
+    }
+    pub(crate) fn save(&self) -> Result<()> {
+        save(Location::Config, APP_NAME, SERVER_PROFILES, self)?;
+        Ok(())
+    }
+    pub(crate) fn selected(&self) -> &ServerProfile {
+        &self.profiles[self.selected]
+    }
+    pub(crate) fn selected_index(&self) -> usize {
+        self.selected
+    }
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.profiles.iter().map(|v| v.name.clone()).collect()
+    }
+    pub(crate) fn select(&mut self, index: usize) {
+        if index < self.profiles.len() {
+            self.selected = index;
+        }
+    }
+    pub(crate) fn add(&mut self, profile: ServerProfile) {
+        self.profiles.push(profile);
+        self.selected = self.profiles.len() - 1;
+    }
+    pub(crate) fn update_selected(&mut self, profile: ServerProfile) {
+        self.profiles[self.selected] = profile;
+    }
+    ///Removes the selected profile, unless it is the last one left as we always need a server to talk to
+    pub(crate) fn remove_selected(&mut self) -> bool {
+        if self.profiles.len() <= 1 {
+            return false;
+        }
+        self.profiles.remove(self.selected);
+        self.selected = self.selected.min(self.profiles.len() - 1);
+        true
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{client::CardRegistry, Result, SIZE};
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, FontRenderer, Graphics},
+};
+
+const WIDTH: f32 = 260.;
+const PADDING: f32 = 10.;
+const TEXT_WIDTH: f32 = WIDTH - PADDING * 2.;
+//the space between the name and cost, the rules text and every keyword
+const SPACING: f32 = 10.;
+//text is drawn from its baseline, so this is how far below the top of a block its first line goes
+const BASELINE: f32 = 15.;
+
+///Places a panel of the given size next to `anchor`, preferring its right side.
+///Flips to the left side if there is no room on the right, and is pushed back inside the screen otherwise
+fn place(anchor: Rectangle, size: Vector) -> Rectangle {
+    let right = anchor.pos.x + anchor.size.x + 5.;
+    let x = if right + size.x <= SIZE.x {
+        right
+    } else {
+        anchor.pos.x - 5. - size.x
+    };
+    let x = x.max(0.).min(SIZE.x - size.x);
+    let y = anchor.pos.y.max(0.).min(SIZE.y - size.y);
+    Rectangle::new(Vector::new(x, y), size)
+}
+
+///Shows the name, cost, rules text and keywords of a card in a panel next to it
+pub(crate) struct CardTooltip {
+    font: FontRenderer,
+}
+
+impl CardTooltip {
+    pub(crate) fn new(font: FontRenderer) -> Self {
+        Self { font }
+    }
+    ///Draws the tooltip for the card with the given id next to `anchor`.
+    ///Nothing is drawn for cards the registry doesn't know about
+    pub(crate) fn draw(
+        &mut self,
+        gfx: &mut Graphics,
+        cards: &CardRegistry,
+        id: &str,
+        anchor: Rectangle,
+    ) -> Result<()> {
+        let card = match cards.get(id) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        //every block gets wrapped to the width of the panel on its own, with some space before it
+        let mut blocks = vec![
+            (card.name.clone(), Color::from_hex("#FFCC00"), 0.),
+            (format!("Costs {} mana", card.cost), Color::from_hex("#7FB8FF"), 0.),
+        ];
+        if !card.description.is_empty() {
+            blocks.push((card.description.clone(), Color::WHITE, SPACING));
+        }
+        for keyword in &card.keywords {
+            if let Some(explanation) = cards.keyword(keyword) {
+                blocks.push((
+                    format!("{}: {}", keyword, explanation),
+                    Color::from_hex("#C0C0C0"),
+                    SPACING,
+                ));
+            }
+        }
+        //laid out without drawing anything first, as the panel has to be drawn before the text on it
+        let mut heights = Vec::with_capacity(blocks.len());
+        for (text, _, _) in &blocks {
+            let size = self
+                .font
+                .layout_glyphs(gfx, text, Some(TEXT_WIDTH), |_, _| {})?;
+            heights.push(size.y);
+        }
+        let text_height: f32 = blocks
+            .iter()
+            .zip(&heights)
+            .map(|((_, _, spacing), height)| spacing + height)
+            .sum();
+        let panel = place(anchor, Vector::new(WIDTH, PADDING * 2. + text_height));
+        gfx.fill_rect(&panel, Color::from_rgba(20, 20, 30, 0.92));
+        gfx.stroke_rect(&panel, Color::from_hex("#C9A227"));
+        let mut y = panel.pos.y + PADDING;
+        for ((text, color, spacing), height) in blocks.iter().zip(heights) {
+            y += spacing;
+            let at = Vector::new(panel.pos.x + PADDING, y + BASELINE);
+            self.font
+                .draw_wrapping(gfx, text, Some(TEXT_WIDTH), *color, at)?;
+            y += height;
+        }
+        Ok(())
+    }
+}
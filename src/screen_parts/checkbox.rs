@@ -0,0 +1,57 @@
+use crate::{Result, Wrapper};
+use mergui::FontStyle;
+use quicksilver::{
+    geom::{Rectangle, Shape, Vector},
+    graphics::Color,
+    input::{Event, MouseButton},
+};
+
+pub(crate) struct Checkbox {
+    ///The location of the label is relative to the box
+    font: FontStyle,
+    label: String,
+    location: Rectangle,
+    checked: bool,
+}
+
+impl Checkbox {
+    pub(crate) fn new(font: FontStyle, label: String, location: Vector, checked: bool) -> Self {
+        Self {
+            font,
+            label,
+            location: Rectangle::new(location, Vector::new(20., 20.)),
+            checked,
+        }
+    }
+    pub(crate) fn is_checked(&self) -> bool {
+        self.checked
+    }
+    pub(crate) fn event(&mut self, event: &Event, wrapper: &Wrapper) {
+        if let Event::PointerInput(x) = event {
+            if x.button() == MouseButton::Left
+                && x.is_down()
+                && self.location.contains(wrapper.cursor_at())
+            {
+                self.checked = !self.checked;
+            }
+        }
+    }
+    pub(crate) fn draw(&mut self, wrapper: &mut Wrapper) -> Result<()> {
+        wrapper.gfx.fill_rect(&self.location, Color::WHITE);
+        wrapper.gfx.stroke_rect(&self.location, Color::BLACK);
+        if self.checked {
+            let inner = Rectangle::new(
+                self.location.pos + Vector::new(4., 4.),
+                self.location.size - Vector::new(8., 8.),
+            );
+            wrapper.gfx.fill_rect(&inner, Color::from_hex("#008B24"));
+        }
+        self.font.font.draw(
+            &mut wrapper.gfx,
+            &self.label,
+            self.font.color,
+            self.location.pos + self.font.location,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Pasting into our own text inputs.
+//!
+//! Browsers only hand out the clipboard through the paste event, which fires a moment after Ctrl+V got pressed
+//! and before the key press reaches our game loop. So the paste event is listened to from the start,
+//! `request_paste` is called when the shortcut is pressed and `take_paste` every frame after.
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use stdweb::{js, unstable::TryInto};
+
+    pub(super) fn listen() {
+        //only needs to happen once, after that every paste on the page ends up in the same place
+        js! {
+            if (!window.card_game_paste_listener) {
+                window.card_game_paste_listener = true;
+                window.card_game_pasted = null;
+                document.addEventListener("paste", function(event) {
+                    window.card_game_pasted = event.clipboardData.getData("text");
+                });
+            }
+        };
+    }
+
+    //the paste event already did the work
+    pub(super) fn request_paste() {}
+
+    pub(super) fn take_paste() -> Option<String> {
+        let pasted = js! {
+            var pasted = window.card_game_pasted || null;
+            window.card_game_pasted = null;
+            return pasted;
+        };
+        pasted.try_into().ok()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static PASTED: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    //the clipboard is read when asked for, so there is nothing to listen to
+    pub(super) fn listen() {}
+
+    pub(super) fn request_paste() {
+        //no clipboard (like on a headless machine) just means there is nothing to paste
+        let text = arboard::Clipboard::new().and_then(|mut v| v.get_text()).ok();
+        PASTED.with(|v| *v.borrow_mut() = text);
+    }
+
+    pub(super) fn take_paste() -> Option<String> {
+        PASTED.with(|v| v.borrow_mut().take())
+    }
+}
+
+///Starts listening for pastes, needs to happen before the first one
+pub(crate) fn listen() {
+    imp::listen()
+}
+
+///Asks for the contents of the clipboard, which `take_paste` returns once they are available
+pub(crate) fn request_paste() {
+    imp::request_paste()
+}
+
+///Gets the text that got pasted since the last call, if any
+pub(crate) fn take_paste() -> Option<String> {
+    imp::take_paste()
+}
+
+///Throws away whatever got pasted while no input was there to take it
+pub(crate) fn discard_paste() {
+    let _ = imp::take_paste();
+}
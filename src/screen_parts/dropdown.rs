@@ -0,0 +1,109 @@
+use crate::{Result, Wrapper};
+use mergui::FontStyle;
+use quicksilver::{
+    geom::{Rectangle, Shape, Vector},
+    graphics::Color,
+    input::{Event, MouseButton},
+};
+
+pub(crate) enum DropdownEvent {
+    ///The event had nothing to do with the dropdown, so others should handle it
+    Ignored,
+    ///The click landed on the dropdown, nothing underneath it should react to it
+    Consumed,
+    ///The user picked a different option, which is also a consumed click
+    Picked(usize),
+}
+
+pub(crate) struct Dropdown {
+    ///The location of the text is relative to each option
+    font: FontStyle,
+    location: Rectangle,
+    options: Vec<String>,
+    selected: usize,
+    open: bool,
+}
+
+impl Dropdown {
+    pub(crate) fn new(
+        font: FontStyle,
+        location: Rectangle,
+        options: Vec<String>,
+        selected: usize,
+    ) -> Self {
+        Self {
+            font,
+            location,
+            options,
+            selected,
+            open: false,
+        }
+    }
+    pub(crate) fn set_options(&mut self, options: Vec<String>, selected: usize) {
+        self.options = options;
+        self.selected = selected;
+    }
+    fn option_location(&self, index: usize) -> Rectangle {
+        Rectangle::new(
+            self.location.pos + Vector::new(0., self.location.size.y * (index + 1) as f32),
+            self.location.size,
+        )
+    }
+    pub(crate) fn event(&mut self, event: &Event, wrapper: &Wrapper) -> DropdownEvent {
+        match event {
+            Event::PointerInput(x) if x.button() == MouseButton::Left && x.is_down() => {}
+            _ => return DropdownEvent::Ignored,
+        }
+        let cursor = wrapper.cursor_at();
+        if self.location.contains(cursor) {
+            self.open = !self.open;
+            return DropdownEvent::Consumed;
+        }
+        if !self.open {
+            return DropdownEvent::Ignored;
+        }
+        self.open = false;
+        let picked = (0..self.options.len()).find(|v| self.option_location(*v).contains(cursor));
+        match picked {
+            Some(x) if x != self.selected => {
+                self.selected = x;
+                DropdownEvent::Picked(x)
+            }
+            Some(_) => DropdownEvent::Consumed,
+            //clicking somewhere else only closes the list, whatever got clicked should still react to it
+            None => DropdownEvent::Ignored,
+        }
+    }
+    pub(crate) fn draw(&mut self, wrapper: &mut Wrapper) -> Result<()> {
+        wrapper.gfx.fill_rect(&self.location, Color::WHITE);
+        wrapper.gfx.stroke_rect(&self.location, Color::BLACK);
+        let current = self.options.get(self.selected).cloned().unwrap_or_default();
+        self.font.font.draw(
+            &mut wrapper.gfx,
+            &format!("{}  v", current),
+            self.font.color,
+            self.location.pos + self.font.location,
+        )?;
+        if !self.open {
+            return Ok(());
+        }
+        let cursor = wrapper.cursor_at();
+        for (key, option) in self.options.iter().enumerate() {
+            let location = self.option_location(key);
+            let background = if location.contains(cursor) {
+                Color::from_hex("#07C739")
+            } else {
+                Color::WHITE
+            };
+            wrapper.gfx.fill_rect(&location, background);
+            wrapper.gfx.stroke_rect(&location, Color::BLACK);
+            self.font.font.draw(
+                &mut wrapper.gfx,
+                option,
+                self.font.color,
+                location.pos + self.font.location,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use quicksilver::input::{Event, Key};
+
+pub(crate) enum FormAction {
+    Submit,
+    Cancel,
+}
+
+pub(crate) trait Focusable {
+    fn is_focused(&self) -> bool;
+    fn set_focused(&mut self, focused: bool);
+}
+
+///Moves keyboard focus between the fields of a form and turns Enter and Escape into actions.
+///
+///The fields themselves keep track of whether they are focused, so clicking on them keeps working as expected.
+#[derive(Default)]
+pub(crate) struct FocusRing {
+    shift_down: bool,
+}
+
+impl FocusRing {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+    pub(crate) fn event(
+        &mut self,
+        event: &Event,
+        fields: &mut [&mut dyn Focusable],
+    ) -> Option<FormAction> {
+        let key = match event {
+            Event::KeyboardInput(x) => x,
+            _ => return None,
+        };
+        match key.key() {
+            Key::LShift | Key::RShift => self.shift_down = key.is_down(),
+            Key::Tab if key.is_down() => self.cycle(fields),
+            Key::Return if key.is_down() => return Some(FormAction::Submit),
+            Key::Escape if key.is_down() => return Some(FormAction::Cancel),
+            _ => {}
+        }
+        None
+    }
+    fn cycle(&self, fields: &mut [&mut dyn Focusable]) {
+        if fields.is_empty() {
+            return;
+        }
+        let len = fields.len();
+        let current = fields.iter().position(|v| v.is_focused());
+        let next = match (current, self.shift_down) {
+            (None, false) => 0,
+            (None, true) => len - 1,
+            (Some(x), false) => (x + 1) % len,
+            (Some(x), true) => (x + len - 1) % len,
+        };
+        for (key, field) in fields.iter_mut().enumerate() {
+            field.set_focused(key == next);
+        }
+    }
+}
@@ -1,55 +1,150 @@
-use crate::Wrapper;
+use super::CardTooltip;
+use crate::{client::CardRegistry, Result, Wrapper};
+use instant::{Duration, Instant};
 use quicksilver::{
     geom::{Rectangle, Shape, Vector},
-    graphics::Image,
+    graphics::{Color, FontRenderer, Graphics, Image},
 };
 
-fn get_location_of_cards(cards: Vec<Image>) -> Vec<(Image, Rectangle)> {
+struct Card {
+    id: String,
+    ///`None` while the art isn't available, a placeholder gets drawn instead
+    art: Option<Image>,
+    location: Rectangle,
+}
+
+fn get_location_of_cards(cards: Vec<(String, Option<Image>)>) -> Vec<Card> {
     cards
         .into_iter()
         .enumerate()
-        .map(|(key, card)| {
+        .map(|(key, (id, art))| {
             let rec_size = Vector::new(135.750_67, 192.);
             let rec_location = Vector::new(8.5375, 6.400_000_6 + (35.84 * key as f32));
-            (card, Rectangle::new(rec_location, rec_size))
+            Card {
+                id,
+                art,
+                location: Rectangle::new(rec_location, rec_size),
+            }
         })
         .collect()
 }
 
+fn draw_card(
+    gfx: &mut Graphics,
+    font: &mut FontRenderer,
+    cards: &CardRegistry,
+    card: &Card,
+    location: Rectangle,
+) -> Result<()> {
+    match &card.art {
+        Some(art) => gfx.draw_image(art, location),
+        None => {
+            gfx.fill_rect(&location, Color::from_hex("#3B2A1A"));
+            gfx.stroke_rect(
+                &Rectangle::new(location.pos + Vector::new(5., 5.), location.size - Vector::new(10., 10.)),
+                Color::from_hex("#C9A227"),
+            );
+            let name = match cards.get(&card.id) {
+                Some(definition) => format!("{} ({})", definition.name, definition.cost),
+                None => card.id.clone(),
+            };
+            //the cards overlap, so the name has to be at the top to stay readable
+            font.draw(gfx, &name, Color::WHITE, location.pos + Vector::new(12., 26.))?;
+        }
+    }
+    Ok(())
+}
+
+//how long the hint about a card being too expensive stays on screen
+const HINT_DURATION: Duration = Duration::from_secs(3);
+
 pub(crate) struct Hand {
     hover_over: Option<usize>,
-    hand: Vec<(Image, Rectangle)>,
+    hand: Vec<Card>,
     clicked: bool,
+    font: FontRenderer,
+    tooltip: CardTooltip,
+    mana: u64,
+    hint: Option<(String, Instant)>,
 }
 
 impl Hand {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(font: FontRenderer, tooltip: CardTooltip) -> Self {
         Self {
             hand: Vec::new(),
             hover_over: None,
             clicked: false,
+            font,
+            tooltip,
+            mana: 0,
+            hint: None,
         }
     }
+    //cards without a known cost are left for the server to judge
+    fn cost_if_unaffordable(&self, card: &Card, cards: &CardRegistry) -> Option<u64> {
+        cards
+            .get(&card.id)
+            .map(|v| v.cost)
+            .filter(|cost| *cost > self.mana)
+    }
+    fn show_hint(&mut self, hint: String) {
+        self.hint = Some((hint, Instant::now()));
+    }
+    ///Tells the player they can't afford the chosen card, saying what it costs if the card definitions know
+    pub(crate) fn show_not_enough_mana(&mut self, chosen: usize, cards: &CardRegistry) {
+        let cost = self
+            .hand
+            .get(chosen)
+            .and_then(|card| cards.get(&card.id))
+            .map(|v| v.cost);
+        self.show_hint(match cost {
+            Some(cost) => format!("Not enough mana (needs {}, have {})", cost, self.mana),
+            None => format!("Not enough mana (have {})", self.mana),
+        });
+    }
     fn set_card_hovering_over(&mut self, cursor_pos: Vector) {
         self.hover_over = self
             .hand
             .iter()
             .enumerate()
             .rev()
-            .map(|(key, card)| (key, card.1))
+            .map(|(key, card)| (key, card.location))
             .find(|(_, card)| card.contains(cursor_pos))
             .map(|(k, _)| k)
     }
-    pub(crate) fn update_hand(&mut self, cards: Vec<Image>, wrapper: &Wrapper) {
+    pub(crate) fn update_hand(
+        &mut self,
+        cards: Vec<(String, Option<Image>)>,
+        mana: u64,
+        wrapper: &Wrapper,
+    ) {
         self.hand = get_location_of_cards(cards);
+        self.mana = mana;
+        self.hint = None;
         self.set_card_hovering_over(wrapper.cursor_at)
     }
+    ///The ids of the cards that are still drawn as a placeholder
+    pub(crate) fn missing_art(&self) -> Vec<String> {
+        let mut missing: Vec<String> = Vec::new();
+        for card in self.hand.iter().filter(|v| v.art.is_none()) {
+            if !missing.contains(&card.id) {
+                missing.push(card.id.clone());
+            }
+        }
+        missing
+    }
+    pub(crate) fn set_art(&mut self, id: &str, art: Image) {
+        for card in self.hand.iter_mut().filter(|v| v.id == id) {
+            card.art = Some(art.clone());
+        }
+    }
     pub(crate) fn event(
         &mut self,
         event: &quicksilver::input::Event,
         wrapper: &mut Wrapper,
     ) -> Option<usize> {
         use quicksilver::input::{Event::*, MouseButton};
+        //cards that cost too much are refused here, rather than sending them just for the server to refuse them
         match event {
             PointerMoved(_) => {
                 self.set_card_hovering_over(wrapper.cursor_at());
@@ -59,7 +154,14 @@ impl Hand {
                 if x.is_down() {
                     if !self.clicked {
                         self.clicked = true;
-                        return self.hover_over;
+                        let chosen = self.hover_over?;
+                        let cards = &wrapper.client.cards;
+                        return if self.cost_if_unaffordable(&self.hand[chosen], cards).is_some() {
+                            self.show_not_enough_mana(chosen, cards);
+                            None
+                        } else {
+                            Some(chosen)
+                        };
                     }
                 } else {
                     self.clicked = false;
@@ -69,18 +171,47 @@ impl Hand {
         }
         None
     }
-    pub(crate) fn draw(&self, wrapper: &mut Wrapper) {
-        for (card, rectangle) in self.hand.iter() {
-            wrapper.gfx.draw_image(card, *rectangle);
+    pub(crate) fn draw(&mut self, wrapper: &mut Wrapper) -> Result<()> {
+        for card in self.hand.iter() {
+            draw_card(
+                &mut wrapper.gfx,
+                &mut self.font,
+                &wrapper.client.cards,
+                card,
+                card.location,
+            )?;
+            if self.cost_if_unaffordable(card, &wrapper.client.cards).is_some() {
+                wrapper
+                    .gfx
+                    .fill_rect(&card.location, Color::from_rgba(0, 0, 0, 0.55));
+            }
+        }
+        if let Some((hint, shown_at)) = &self.hint {
+            if shown_at.elapsed() < HINT_DURATION {
+                self.font.draw(
+                    &mut wrapper.gfx,
+                    hint,
+                    Color::from_hex("#FFCC00"),
+                    Vector::new(27.32, 655.),
+                )?;
+            }
         }
-        if let Some(card) = self.hover_over.and_then(|v| self.hand.get(v)) {
-            wrapper.gfx.draw_image(
-                &card.0,
-                Rectangle::new(
-                    Vector::new(card.1.pos.x + card.1.size.x + 5., card.1.pos.y),
-                    card.1.size() * 1.2,
-                ),
+        let hand = &self.hand;
+        if let Some(card) = self.hover_over.and_then(|v| hand.get(v)) {
+            let enlarged = Rectangle::new(
+                Vector::new(card.location.pos.x + card.location.size.x + 5., card.location.pos.y),
+                card.location.size() * 1.2,
             );
+            draw_card(
+                &mut wrapper.gfx,
+                &mut self.font,
+                &wrapper.client.cards,
+                card,
+                enlarged,
+            )?;
+            self.tooltip
+                .draw(&mut wrapper.gfx, &wrapper.client.cards, &card.id, enlarged)?;
         }
+        Ok(())
     }
 }
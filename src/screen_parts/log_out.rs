@@ -0,0 +1,21 @@
+use crate::{Result, Wrapper};
+use mergui::{widgets::ButtonConfig, FontStyle, MFont};
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::Color,
+};
+
+pub(crate) fn log_out_button(wrapper: &Wrapper, location: Vector) -> Result<ButtonConfig> {
+    Ok(ButtonConfig {
+        text: "Log out".into(),
+        font_style: FontStyle {
+            font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
+            location: Vector::new(10., 20.),
+            color: Color::WHITE,
+        },
+        background: wrapper.button_image.clone(),
+        background_location: Rectangle::new(location, Vector::new(100., 30.)),
+        blend_color: Some(Color::from_hex("#8B0000")),
+        hover_color: Some(Color::RED),
+    })
+}
@@ -1,2 +1,17 @@
+mod card_tooltip;
+mod checkbox;
+mod clipboard;
+mod dropdown;
+mod focus;
 mod hand;
+mod log_out;
+mod server_status;
+mod text_input;
+pub(crate) use card_tooltip::CardTooltip;
+pub(crate) use checkbox::Checkbox;
+pub(crate) use dropdown::{Dropdown, DropdownEvent};
+pub(crate) use focus::{FocusRing, Focusable, FormAction};
 pub(crate) use hand::Hand;
+pub(crate) use log_out::log_out_button;
+pub(crate) use server_status::ServerStatus;
+pub(crate) use text_input::{MaskConfig, TextInput, TextInputConfig};
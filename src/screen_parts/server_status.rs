@@ -0,0 +1,87 @@
+use crate::{client::ClientResult, Client, Result, Wrapper};
+use futures::FutureExt;
+use instant::{Duration, Instant};
+use mergui::MFont;
+use quicksilver::{
+    geom::{Circle, Vector},
+    graphics::Color,
+};
+use std::{future::Future, pin::Pin};
+
+const PING_EVERY: Duration = Duration::from_secs(5);
+//gives the user a moment to finish typing before we start pinging a half written address
+const WAIT_AFTER_CHANGE: Duration = Duration::from_millis(500);
+
+enum Status {
+    Checking,
+    Reachable(Duration),
+    Unreachable,
+}
+
+///Periodically pings a server and shows whether it is up
+pub(crate) struct ServerStatus {
+    font: MFont,
+    location: Vector,
+    url: String,
+    status: Status,
+    next_ping: Instant,
+    //the ping that is still waiting on an answer, polled every frame so it never holds up drawing
+    pending: Option<Pin<Box<dyn Future<Output = ClientResult<Duration>>>>>,
+}
+
+impl ServerStatus {
+    pub(crate) fn new(font: MFont, location: Vector) -> Self {
+        Self {
+            font,
+            location,
+            url: String::new(),
+            status: Status::Checking,
+            next_ping: Instant::now(),
+            pending: None,
+        }
+    }
+    pub(crate) fn update(&mut self, client: &Client, url: String) {
+        if url != self.url {
+            self.url = url;
+            self.status = Status::Checking;
+            self.next_ping = Instant::now() + WAIT_AFTER_CHANGE;
+            //an answer for the old address says nothing about the new one
+            self.pending = None;
+        }
+        if let Some(ping) = &mut self.pending {
+            if let Some(res) = ping.as_mut().now_or_never() {
+                self.status = match res {
+                    Ok(x) => Status::Reachable(x),
+                    Err(_) => Status::Unreachable,
+                };
+                self.pending = None;
+                self.next_ping = Instant::now() + PING_EVERY;
+            }
+            return;
+        }
+        if self.url.is_empty() || Instant::now() < self.next_ping {
+            return;
+        }
+        self.pending = Some(Box::pin(client.ping(&self.url)));
+    }
+    pub(crate) fn draw(&mut self, wrapper: &mut Wrapper) -> Result<()> {
+        let (color, text) = match self.status {
+            Status::Checking => (Color::from_hex("#746868"), "Checking...".to_owned()),
+            Status::Reachable(x) => (
+                Color::from_hex("#008B24"),
+                format!("Online ({} ms)", x.as_millis()),
+            ),
+            Status::Unreachable => (Color::RED, "Unreachable".to_owned()),
+        };
+        wrapper
+            .gfx
+            .fill_circle(&Circle::new(self.location + Vector::new(6., -6.), 6.), color);
+        self.font.draw(
+            &mut wrapper.gfx,
+            &text,
+            color,
+            self.location + Vector::new(18., 0.),
+        )?;
+        Ok(())
+    }
+}
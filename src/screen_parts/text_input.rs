@@ -0,0 +1,262 @@
+use super::{clipboard, Focusable};
+use crate::{Result, Wrapper};
+use instant::Instant;
+use mergui::FontStyle;
+use quicksilver::{
+    geom::{Rectangle, Shape, Vector},
+    graphics::Color,
+    input::{Event, Key, MouseButton},
+};
+
+const MASK_CHAR: char = '\u{2022}';
+//how long the caret stays visible and hidden while blinking
+const BLINK_MS: u128 = 500;
+
+pub(crate) struct MaskConfig {
+    ///Adds a button next to the input to temporarily show what got typed
+    pub(crate) show_toggle: bool,
+}
+
+pub(crate) struct TextInputConfig {
+    ///The location of the text is relative to the location of the input
+    pub(crate) font: FontStyle,
+    pub(crate) placeholder: Option<(String, Color)>,
+    pub(crate) location: Rectangle,
+    pub(crate) start_value: Option<String>,
+    pub(crate) mask: Option<MaskConfig>,
+}
+
+///A text input that we draw ourselves, for the cases where mergui's input isn't flexible enough.
+///
+///mergui's input can't be focused or unfocused from our side, which the focus ring needs,
+///and it has no way to hide what gets typed into it, which password fields need.
+pub(crate) struct TextInput {
+    font: FontStyle,
+    placeholder: Option<(String, Color)>,
+    location: Rectangle,
+    value: String,
+    //in characters rather than bytes, so it can't end up inside of a character
+    cursor: usize,
+    //where the caret goes for every cursor position, as found while drawing the text
+    caret_x: Vec<f32>,
+    //the caret is always shown right after typing or moving it, and blinks from then on
+    blink_start: Instant,
+    modifier_down: bool,
+    mask: Option<MaskConfig>,
+    revealed: bool,
+    focused: bool,
+}
+
+impl TextInput {
+    pub(crate) fn new(config: TextInputConfig) -> Self {
+        clipboard::listen();
+        let value = config.start_value.unwrap_or_default();
+        Self {
+            font: config.font,
+            placeholder: config.placeholder,
+            location: config.location,
+            cursor: value.chars().count(),
+            value,
+            caret_x: Vec::new(),
+            blink_start: Instant::now(),
+            modifier_down: false,
+            mask: config.mask,
+            revealed: false,
+            focused: false,
+        }
+    }
+    pub(crate) fn get(&self) -> String {
+        self.value.clone()
+    }
+    pub(crate) fn set(&mut self, value: String) {
+        self.cursor = value.chars().count();
+        self.value = value;
+    }
+    pub(crate) fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| self.value.len())
+    }
+    fn move_cursor(&mut self, to: usize) {
+        self.cursor = to.min(self.value.chars().count());
+        self.blink_start = Instant::now();
+    }
+    fn insert(&mut self, text: &str) {
+        let at = self.byte_index(self.cursor);
+        self.value.insert_str(at, text);
+        self.move_cursor(self.cursor + text.chars().count());
+    }
+    fn remove(&mut self, cursor: usize) {
+        if cursor < self.value.chars().count() {
+            let at = self.byte_index(cursor);
+            self.value.remove(at);
+        }
+    }
+    //picks the cursor position closest to where got clicked
+    fn cursor_from_x(&self, x: f32) -> usize {
+        self.caret_x
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - x).abs().partial_cmp(&(*b - x).abs()).unwrap())
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| self.value.chars().count())
+    }
+    fn set_focus(&mut self, focused: bool) {
+        if focused != self.focused {
+            //a paste from before this input got focused wasn't meant for it
+            clipboard::discard_paste();
+        }
+        self.focused = focused;
+    }
+    fn toggle_location(&self) -> Option<Rectangle> {
+        match &self.mask {
+            Some(MaskConfig { show_toggle: true }) => Some(Rectangle::new(
+                Vector::new(
+                    self.location.pos.x + self.location.size.x + 5.,
+                    self.location.pos.y,
+                ),
+                Vector::new(55., self.location.size.y),
+            )),
+            _ => None,
+        }
+    }
+    fn shown_value(&self) -> String {
+        if self.mask.is_some() && !self.revealed {
+            self.value.chars().map(|_| MASK_CHAR).collect()
+        } else {
+            self.value.clone()
+        }
+    }
+    pub(crate) fn event(&mut self, event: &Event, wrapper: &Wrapper) {
+        match event {
+            Event::PointerInput(x) if x.button() == MouseButton::Left && x.is_down() => {
+                let cursor = wrapper.cursor_at();
+                if self
+                    .toggle_location()
+                    .map(|v| v.contains(cursor))
+                    .unwrap_or(false)
+                {
+                    self.revealed = !self.revealed;
+                } else {
+                    self.set_focus(self.location.contains(cursor));
+                    if self.focused {
+                        self.move_cursor(self.cursor_from_x(cursor.x));
+                    }
+                }
+            }
+            //shortcuts like Ctrl+V come in as control characters, while AltGr (reported as Ctrl+Alt on windows)
+            //types normal characters like @ and {, so only the control characters get skipped
+            Event::ReceivedCharacter(x) if self.focused => {
+                let character = x.character();
+                if !character.is_control() {
+                    self.insert(&character.to_string());
+                }
+            }
+            Event::KeyboardInput(x) => match x.key() {
+                //Cmd is what gets used for shortcuts on macs
+                Key::LControl | Key::RControl | Key::LWin | Key::RWin => {
+                    self.modifier_down = x.is_down()
+                }
+                _ if !self.focused || !x.is_down() => {}
+                Key::Back if self.cursor > 0 => {
+                    self.remove(self.cursor - 1);
+                    self.move_cursor(self.cursor - 1);
+                }
+                Key::Delete => {
+                    self.remove(self.cursor);
+                    self.move_cursor(self.cursor);
+                }
+                Key::Left => self.move_cursor(self.cursor.saturating_sub(1)),
+                Key::Right => self.move_cursor(self.cursor + 1),
+                Key::Home => self.move_cursor(0),
+                Key::End => self.move_cursor(self.value.chars().count()),
+                Key::V if self.modifier_down => clipboard::request_paste(),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    pub(crate) fn draw(&mut self, wrapper: &mut Wrapper) -> Result<()> {
+        if self.focused {
+            if let Some(pasted) = clipboard::take_paste() {
+                //inputs are a single line, so newlines get turned into spaces
+                let pasted: String = pasted
+                    .chars()
+                    .map(|v| if v.is_control() { ' ' } else { v })
+                    .collect();
+                self.insert(&pasted);
+            }
+        }
+        let border = if self.focused {
+            Color::BLUE
+        } else {
+            Color::BLACK
+        };
+        wrapper.gfx.fill_rect(&self.location, Color::WHITE);
+        wrapper.gfx.stroke_rect(&self.location, border);
+        let text_location = self.location.pos + self.font.location;
+        let shown_value = self.shown_value();
+        self.caret_x.clear();
+        self.caret_x.push(text_location.x);
+        match &self.placeholder {
+            Some((placeholder, color)) if self.value.is_empty() => {
+                self.font
+                    .font
+                    .draw(&mut wrapper.gfx, placeholder, *color, text_location)?;
+            }
+            _ => {
+                //drawn one character at a time, so we know where the caret goes between each of them
+                let mut x = text_location.x;
+                for character in shown_value.chars() {
+                    let size = self.font.font.draw(
+                        &mut wrapper.gfx,
+                        &character.to_string(),
+                        self.font.color,
+                        Vector::new(x, text_location.y),
+                    )?;
+                    x += size.x;
+                    self.caret_x.push(x);
+                }
+            }
+        }
+        let blink_on = (self.blink_start.elapsed().as_millis() / BLINK_MS) % 2 == 0;
+        if self.focused && blink_on {
+            let x = self.caret_x.get(self.cursor).copied().unwrap_or(text_location.x);
+            wrapper.gfx.fill_rect(
+                &Rectangle::new(
+                    Vector::new(x, self.location.pos.y + 4.),
+                    Vector::new(1., self.location.size.y - 8.),
+                ),
+                self.font.color,
+            );
+        }
+        if let Some(toggle) = self.toggle_location() {
+            wrapper.gfx.fill_rect(&toggle, Color::from_hex("#008B24"));
+            self.font.font.draw(
+                &mut wrapper.gfx,
+                if self.revealed { "Hide" } else { "Show" },
+                Color::WHITE,
+                toggle.pos + self.font.location,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Focusable for TextInput {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+    fn set_focused(&mut self, focused: bool) {
+        if focused && !self.focused {
+            self.move_cursor(self.value.chars().count());
+        }
+        self.set_focus(focused);
+    }
+}
@@ -1,11 +1,18 @@
-use super::{BattleOver, Screen};
+use super::{show, Login, Screen};
 use async_trait::async_trait;
-use quicksilver::geom::{Circle, Vector};
+use mergui::{
+    channels::{BasicClickable, ConcealerReturn},
+    widgets::{ButtonConfig, ConcealerConfig},
+    FontStyle, MFont, Response,
+};
+use quicksilver::geom::{Circle, Rectangle, Vector};
 use quicksilver::graphics::{Color, FontRenderer, VectorFont};
 
 use crate::{
     animations::{calc_points, RuneAnimation},
-    screen_parts::Hand,
+    client::{mana_amount, PendingArt, ReturnBattleWithImages},
+    flow::{self, Played, Step},
+    screen_parts::{log_out_button, CardTooltip, Hand},
     Wrapper, SIZE,
 };
 
@@ -36,21 +43,55 @@ pub struct Battle {
     enemy_mana: String,
     player_mana: String,
     hand_2: Hand,
+    art_retry: Option<PendingArt>,
+    _menu: Response<ConcealerReturn>,
+    log_out: Response<BasicClickable>,
 }
 
 impl Battle {
-    pub(crate) async fn new(char_id : i64, wrapper: &mut Wrapper) -> crate::Result<Battle> {
+    pub(crate) async fn new(
+        char_id: i64,
+        current: ReturnBattleWithImages,
+        wrapper: &mut Wrapper,
+    ) -> crate::Result<Battle> {
         let outer_radius = 307.200_000_000_000_05;
         let outer_points = calc_points(outer_radius, 8, 10.0, |x: f64, y: f64, _| {
             (x + 683.85375, y + 384.639_997_44 /*300.5f64*/)
         });
-        let mut hand = Hand::new();
+        let font = VectorFont::load("font.ttf").await?;
+        let mut hand = Hand::new(
+            font.to_renderer(&wrapper.gfx, 16.0)?,
+            CardTooltip::new(font.to_renderer(&wrapper.gfx, 16.0)?),
+        );
 
-        let current = wrapper.client.new_battle(char_id,&wrapper.gfx).await?;
         let (current, cards) = (current.battle, current.images);
-        hand.update_hand(cards, wrapper);
+        hand.update_hand(
+            current.hand.iter().cloned().zip(cards).collect(),
+            mana_amount(current.mana),
+            wrapper,
+        );
 
-        let font = VectorFont::load("font.ttf").await?;
+        let mut menu_layer = wrapper.context.add_singular_layer();
+        let log_out = menu_layer.add_widget(log_out_button(wrapper, Vector::new(1246., 680.))?);
+        let mut layer = wrapper.context.add_layer();
+        let menu = layer.add_widget(ConcealerConfig {
+            button: ButtonConfig {
+                text: "Menu".into(),
+                font_style: FontStyle {
+                    font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
+                    location: Vector::new(10., 20.),
+                    color: Color::WHITE,
+                },
+                background: wrapper.button_image.clone(),
+                background_location: Rectangle::new(
+                    Vector::new(1246., 720.),
+                    Vector::new(100., 30.),
+                ),
+                blend_color: Some(Color::from_hex("#008B24")),
+                hover_color: Some(Color::from_hex("#07C739")),
+            },
+            layer: menu_layer,
+        });
 
         Ok(Battle {
             player_mana: current.mana.to_string(),
@@ -64,7 +105,10 @@ impl Battle {
             stat_font: font.to_renderer(&wrapper.gfx, 25.0)?,
             hexa_runes: RuneAnimation::new(179.2),
             hand_2: hand,
-            character_id : char_id
+            art_retry: None,
+            character_id : char_id,
+            _menu: menu,
+            log_out,
         })
     }
     async fn play_card(
@@ -72,16 +116,24 @@ impl Battle {
         wrapper: &mut Wrapper,
         chosen: usize,
     ) -> crate::Result<Option<Box<dyn Screen>>> {
-        let battle = wrapper.client.do_turn(chosen,self.character_id, &wrapper.gfx).await?;
-        let battle = match battle {
-            crate::client::AfterTurn::Over => {
-                return Ok(Some(Box::new(BattleOver::new(wrapper).await?)))
+        let battle = match flow::play_card(&mut wrapper.client, self.character_id, chosen, &wrapper.gfx).await? {
+            Played::Turn(x) => x,
+            Played::Refused => {
+                self.hand_2.show_not_enough_mana(chosen, &wrapper.client.cards);
+                return Ok(None);
             }
-            crate::client::AfterTurn::NewTurn(x) => x,
-            crate::client::AfterTurn::NoTurnHappened => return Ok(None),
+            Played::Over => return Ok(Some(show(Step::BattleOver, wrapper).await?)),
         };
+        self.set_battle(battle, wrapper);
+        Ok(None)
+    }
+    fn set_battle(&mut self, battle: ReturnBattleWithImages, wrapper: &Wrapper) {
         let (battle, hand) = (battle.battle, battle.images);
-        self.hand_2.update_hand(hand, &wrapper);
+        self.hand_2.update_hand(
+            battle.hand.iter().cloned().zip(hand).collect(),
+            mana_amount(battle.mana),
+            &wrapper,
+        );
         self.enemy_hand_size = format!("S: {}", battle.enemy_hand_size);
         self.enemy_hp = format!("HP: {}", battle.enemy_hp);
         self.player_hp = format!("HP: {}", battle.player_hp);
@@ -90,7 +142,6 @@ impl Battle {
         self.enemy_mana = battle.enemy_mana.to_string();
         self.player_mana = battle.mana.to_string();
         self.hexa_runes.set_state(battle.hexa_runes);
-        Ok(None)
     }
 }
 
@@ -128,7 +179,7 @@ impl Screen for Battle {
             .gfx
             .stroke_path(&[(0., 0.).into(), SIZE], Color::BLUE);
 
-        self.hand_2.draw(wrapper);
+        self.hand_2.draw(wrapper)?;
         let renderer = &mut self.stat_font;
         let offset = Vector::new(27.32, 729.6);
         renderer.draw(&mut wrapper.gfx, &self.player_hp, Color::RED, offset)?;
@@ -143,7 +194,27 @@ impl Screen for Battle {
         renderer.draw(&mut wrapper.gfx, &self.enemy_mana, Color::RED, offset)?;
         Ok(())
     }
-    async fn update(&mut self, _: &mut crate::Wrapper) -> crate::Result<Option<Box<dyn Screen>>> {
+    async fn update(&mut self, wrapper: &mut crate::Wrapper) -> crate::Result<Option<Box<dyn Screen>>> {
+        if self.log_out.channel.has_clicked() {
+            return Ok(Some(Box::new(Login::log_out(wrapper).await?)));
+        }
+        //the download is only polled here, so the battle keeps being drawn while it runs
+        match &mut self.art_retry {
+            Some(pending) => {
+                if let Some(found) = wrapper.client.poll_card_art(pending, &wrapper.gfx) {
+                    for (id, art) in found {
+                        self.hand_2.set_art(&id, art);
+                    }
+                    self.art_retry = None;
+                }
+            }
+            None => {
+                let missing = self.hand_2.missing_art();
+                if !missing.is_empty() {
+                    self.art_retry = wrapper.client.retry_card_art(&missing);
+                }
+            }
+        }
         Ok(None)
     }
 
@@ -1,7 +1,7 @@
-use super::Screen;
-use crate::{Result as CResult, Wrapper};
+use super::{Login, Screen};
+use crate::{screen_parts::log_out_button, Result as CResult, Wrapper};
 use async_trait::async_trait;
-use mergui::MFont;
+use mergui::{channels::BasicClickable, MFont, Response};
 use quicksilver::{
     geom::Vector,
     graphics::{Color, VectorFont},
@@ -9,13 +9,16 @@ use quicksilver::{
 
 pub(crate) struct BattleOver {
     font: MFont,
+    log_out: Response<BasicClickable>,
 }
 
 impl BattleOver {
     pub(crate) async fn new(wrapper: &mut Wrapper) -> CResult<Self> {
         let ttf = VectorFont::load("font.ttf").await?;
         let font = MFont::from_font(&ttf, &wrapper.gfx, 30.0)?;
-        Ok(BattleOver { font })
+        let mut layer = wrapper.context.add_layer();
+        let log_out = layer.add_widget(log_out_button(wrapper, Vector::new(583., 360.))?);
+        Ok(BattleOver { font, log_out })
     }
 }
 
@@ -31,7 +34,10 @@ impl Screen for BattleOver {
         )?;
         Ok(())
     }
-    async fn update(&mut self, _: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+    async fn update(&mut self, wrapper: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        if self.log_out.channel.has_clicked() {
+            return Ok(Some(Box::new(Login::log_out(wrapper).await?)));
+        }
         Ok(None)
     }
 }
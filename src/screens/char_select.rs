@@ -1,5 +1,5 @@
-use super::{Battle, Screen};
-use crate::{Result, Wrapper};
+use super::{show, Login, Screen};
+use crate::{flow, screen_parts::log_out_button, Result, Wrapper};
 use async_trait::async_trait;
 use mergui::{channels::BasicClickable, widgets::ButtonConfig, FontStyle, MFont, Response};
 use quicksilver::{
@@ -7,64 +7,55 @@ use quicksilver::{
     graphics::Color,
 };
 
-enum ButtonType {
-    Old(i64),
-    New,
-}
 pub(crate) struct CharacterSelect {
-    button: (ButtonType, Response<BasicClickable>),
+    ///`None` if there is no character yet, which the button makes
+    character: Option<i64>,
+    button: Response<BasicClickable>,
+    log_out: Response<BasicClickable>,
 }
 
 impl CharacterSelect {
-    pub(crate) async fn new(wrapper: &mut Wrapper) -> Result<Self> {
-        let characters = wrapper.client.get_characters().await?;
+    pub(crate) async fn new(character: Option<i64>, wrapper: &mut Wrapper) -> Result<Self> {
         let mut layer = wrapper.context.add_layer();
 
-        let v = characters
-            .characters
-            .get(0)
-            .map(|v| {
-                Result::<_>::Ok((
-                    ButtonType::Old(*v),
-                    layer.add_widget(ButtonConfig {
-                        text: "Current character".into(),
-                        font_style: FontStyle {
-                            font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
-                            location: Vector::new(70., 25.),
-                            color: Color::WHITE,
-                        },
-                        background: wrapper.button_image.clone(),
-                        background_location: Rectangle::new(
-                            Vector::new(653., 304.),
-                            Vector::new(300., 40.),
-                        ),
-                        blend_color: Some(Color::GREEN),
-                        hover_color: Some(Color::CYAN),
-                    }),
-                ))
-            })
-            .unwrap_or_else(|| {
-                Ok((
-                    ButtonType::New,
-                    layer.add_widget(ButtonConfig {
-                        text: "New character".into(),
-                        font_style: FontStyle {
-                            font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
-                            location: Vector::new(10., 20.),
-                            color: Color::WHITE,
-                        },
-                        background: wrapper.button_image.clone(),
-                        background_location: Rectangle::new(
-                            Vector::new(653., 304.),
-                            Vector::new(100., 30.),
-                        ),
-                        blend_color: Some(Color::GREEN),
-                        hover_color: Some(Color::CYAN),
-                    }),
-                ))
-            })?;
-        //layer.add_widget(widget_config)
-        Ok(Self { button: v, })
+        let button = match character {
+            Some(_) => layer.add_widget(ButtonConfig {
+                text: "Current character".into(),
+                font_style: FontStyle {
+                    font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
+                    location: Vector::new(70., 25.),
+                    color: Color::WHITE,
+                },
+                background: wrapper.button_image.clone(),
+                background_location: Rectangle::new(
+                    Vector::new(653., 304.),
+                    Vector::new(300., 40.),
+                ),
+                blend_color: Some(Color::GREEN),
+                hover_color: Some(Color::CYAN),
+            }),
+            None => layer.add_widget(ButtonConfig {
+                text: "New character".into(),
+                font_style: FontStyle {
+                    font: MFont::from_font(&wrapper.font, &wrapper.gfx, 20.0)?,
+                    location: Vector::new(10., 20.),
+                    color: Color::WHITE,
+                },
+                background: wrapper.button_image.clone(),
+                background_location: Rectangle::new(
+                    Vector::new(653., 304.),
+                    Vector::new(100., 30.),
+                ),
+                blend_color: Some(Color::GREEN),
+                hover_color: Some(Color::CYAN),
+            }),
+        };
+        let log_out = layer.add_widget(log_out_button(wrapper, Vector::new(1246., 20.))?);
+        Ok(Self {
+            character,
+            button,
+            log_out,
+        })
     }
 }
 
@@ -75,17 +66,12 @@ impl Screen for CharacterSelect {
         Ok(())
     }
     async fn update(&mut self, wrapper: &mut Wrapper) -> crate::Result<Option<Box<dyn Screen>>> {
-        if self.button.1.channel.has_clicked() {
-            let char_id = match self.button.0 {
-                ButtonType::Old(x) => {
-                    x
-                }
-                ButtonType::New => {
-                    wrapper.client.create_character().await?.id
-                }
-            };
-            let battle = Battle::new(char_id, wrapper).await?;
-            Ok(Some(Box::new(battle)))
+        if self.log_out.channel.has_clicked() {
+            return Ok(Some(Box::new(Login::log_out(wrapper).await?)));
+        }
+        if self.button.channel.has_clicked() {
+            let next = flow::pick_character(&mut wrapper.client, self.character, &wrapper.gfx).await?;
+            Ok(Some(show(next, wrapper).await?))
         } else {
             Ok(None)
         }
@@ -1,35 +1,84 @@
-use super::{CharacterSelect, Screen};
-use crate::{Result as CResult, Wrapper, APP_NAME};
+use super::{show, Register, Screen};
+use crate::{
+    client::ClientError,
+    flow, saved,
+    saved::ServerProfile,
+    screen_parts::{
+        Checkbox, Dropdown, DropdownEvent, FocusRing, Focusable, FormAction, MaskConfig,
+        ServerStatus, TextInput, TextInputConfig,
+    },
+    Result as CResult, Wrapper,
+};
 use async_trait::async_trait;
 use mergui::{
-    channels::{BasicClickable, ConcealerReturn, InputChannel},
-    core::Text,
-    widgets::{
-        input::{InputConfig, PlaceholderConfig},
-        ButtonConfig, ConcealerConfig,
-    },
-    FontStyle, MFont, Response,
+    channels::BasicClickable, core::Text, widgets::ButtonConfig, FontStyle, MFont, Response,
 };
 use quicksilver::{
     geom::{Rectangle, Vector},
     graphics::{Color, Image, VectorFont},
-    saving::{save, Location},
 };
 
+//only exist while the advanced section is shown, so the buttons disappear together with it
+struct ProfileButtons {
+    add: Response<BasicClickable>,
+    save: Response<BasicClickable>,
+    delete: Response<BasicClickable>,
+    clear_cache: Response<BasicClickable>,
+}
+
 pub(crate) struct Login {
     _text: Response<()>,
-    name_input: Response<InputChannel>,
-    password_input: Response<InputChannel>,
+    name_input: TextInput,
+    password_input: TextInput,
     login_button: Response<BasicClickable>,
-    _concealer: Response<ConcealerReturn>,
-    server_address: Response<InputChannel>,
+    register_button: Response<BasicClickable>,
+    advanced_button: Response<BasicClickable>,
+    show_advanced: bool,
+    server_picker: Dropdown,
+    server_status: ServerStatus,
+    profile_name: TextInput,
+    profile_url: TextInput,
+    profile_buttons: Option<ProfileButtons>,
+    button_font: FontStyle,
+    button_background: Image,
+    remember_me: Checkbox,
+    focus: FocusRing,
+    error_font: MFont,
+    error: Option<String>,
+}
+
+pub(crate) fn describe_error(error: &ClientError) -> String {
+    match error {
+        ClientError::Server { message, .. } => message.clone(),
+        ClientError::Transport(_) => "Server unreachable. Check the address under Advanced.".into(),
+        ClientError::Timeout => "The server took too long to respond.".into(),
+        ClientError::Status(status) => format!("Server responded with status {}", status),
+        ClientError::Decode(_) => "The server sent something we could not understand.".into(),
+        ClientError::Incompatible { .. } => format!("Client and server are incompatible. {}", error),
+        ClientError::Asset { path, .. } => format!("Could not load {}", path),
+    }
 }
 
 impl Login {
+    pub(crate) async fn log_out(wrapper: &mut Wrapper) -> CResult<Self> {
+        let res = wrapper.client.log_out().await;
+        saved::forget_token(&wrapper.client.base_url)?;
+        match res {
+            Ok(()) => Self::new(wrapper).await,
+            //we are logged out locally either way, the user just needs to know the server may not agree
+            Err(x) => Self::with_error(wrapper, describe_error(&x)).await,
+        }
+    }
+    pub(crate) async fn with_error(wrapper: &mut Wrapper, error: String) -> CResult<Self> {
+        let mut login = Self::new(wrapper).await?;
+        login.error = Some(error);
+        Ok(login)
+    }
     pub(crate) async fn new(wrapper: &mut Wrapper) -> CResult<Self> {
-        let mut layer = wrapper.context.add_layer();
+        let layer = wrapper.context.add_layer();
         let ttf = VectorFont::load("font.ttf").await?;
         let font = MFont::from_font(&ttf, &wrapper.gfx, 30.0)?;
+        let error_font = MFont::from_font(&ttf, &wrapper.gfx, 18.0)?;
         let basic_font_style = FontStyle {
             font: font.clone(),
             location: Vector::new(350., 40.),
@@ -45,33 +94,27 @@ impl Login {
             font: MFont::from_font(&ttf, &wrapper.gfx, 20.0)?,
             ..basic_font_style.clone()
         };
-        let placeholder_font = FontStyle {
-            color: Color::from_hex("#746868"),
+        let text_input_font = FontStyle {
+            location: Vector::new(5., 19.),
             ..input_font.clone()
         };
-        let conf = InputConfig {
-            font: input_font.clone(),
-            placeholder: Some(PlaceholderConfig {
-                font: placeholder_font.clone(),
-                text: "Username".into(),
-            }), //Option<PlaceholderConfig>,
+        let placeholder_color = Color::from_hex("#746868");
+        let mut name_input = TextInput::new(TextInputConfig {
+            font: text_input_font.clone(),
+            placeholder: Some(("Username".into(), placeholder_color)),
             location: Rectangle::new(Vector::new(200., 200.), Vector::new(300., 25.)),
             start_value: None,
-            cursor_config: Default::default(),
-        };
-        let name_input = wrapper.context.add_widget(conf, &layer).unwrap();
+            mask: None,
+        });
+        name_input.set_focused(true);
 
-        let conf = InputConfig {
-            font: input_font.clone(),
-            placeholder: Some(PlaceholderConfig {
-                font: placeholder_font,
-                text: "Password".into(),
-            }), //Option<PlaceholderConfig>,
+        let password_input = TextInput::new(TextInputConfig {
+            font: text_input_font.clone(),
+            placeholder: Some(("Password".into(), placeholder_color)),
             location: Rectangle::new(Vector::new(200., 230.), Vector::new(300., 25.)),
             start_value: None,
-            cursor_config: Default::default(),
-        };
-        let password_input = wrapper.context.add_widget(conf, &layer).unwrap();
+            mask: Some(MaskConfig { show_toggle: true }),
+        });
 
         let button_background = Image::load(&wrapper.gfx, "button.png").await?;
 
@@ -89,88 +132,331 @@ impl Login {
         };
         let login_button = wrapper.context.add_widget(conf, &layer).unwrap();
 
-        let mut secret_layer = wrapper.context.add_singular_layer();
-
-        let server_address = InputConfig {
-            font: input_font.clone(),
-            placeholder: None,
-            location: Rectangle::new(Vector::new(200., 295.), Vector::new(300., 25.)),
-            start_value: Some(wrapper.client.base_url.clone()),
-            cursor_config: Default::default(),
+        let conf = ButtonConfig {
+            text: "Register".into(),
+            font_style: FontStyle {
+                color: Color::WHITE,
+                location: Vector::new(10., 20.),
+                ..input_font.clone()
+            },
+            background: button_background.clone(),
+            background_location: Rectangle::new(Vector::new(315., 260.), Vector::new(110., 30.)),
+            blend_color: Some(Color::from_hex("#008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
         };
-        let server_address = secret_layer.add_widget(server_address);
+        let register_button = wrapper.context.add_widget(conf, &layer).unwrap();
+
+        let server_picker = Dropdown::new(
+            text_input_font.clone(),
+            Rectangle::new(Vector::new(200., 295.), Vector::new(300., 25.)),
+            wrapper.profiles.names(),
+            wrapper.profiles.selected_index(),
+        );
+        let server_status = ServerStatus::new(error_font.clone(), Vector::new(510., 313.));
+        let profile_name = TextInput::new(TextInputConfig {
+            font: text_input_font.clone(),
+            placeholder: Some(("Profile name".into(), placeholder_color)),
+            location: Rectangle::new(Vector::new(200., 365.), Vector::new(300., 25.)),
+            start_value: Some(wrapper.profiles.selected().name.clone()),
+            mask: None,
+        });
+        let profile_url = TextInput::new(TextInputConfig {
+            font: text_input_font.clone(),
+            placeholder: Some(("Server address".into(), placeholder_color)),
+            location: Rectangle::new(Vector::new(200., 395.), Vector::new(300., 25.)),
+            start_value: Some(wrapper.profiles.selected().url.clone()),
+            mask: None,
+        });
 
-        let concealer_config = ConcealerConfig {
-            button: ButtonConfig {
-                text: "Advanced".into(),
-                font_style: FontStyle {
-                    location: Vector::new(10., 20.),
-                    color: Color::WHITE,
-                    ..input_font
-                },
-                background: button_background,
-                background_location: (Rectangle::new(
-                    Vector::new(200., 260.),
-                    Vector::new(110., 30.),
-                )),
-                blend_color: Some(Color::from_hex("008B24")),
-                hover_color: Some(Color::from_hex("#07C739")),
+        let remember_me = Checkbox::new(
+            FontStyle {
+                location: Vector::new(25., 16.),
+                ..text_input_font.clone()
             },
-            layer: secret_layer,
+            "Remember me".into(),
+            Vector::new(200., 330.),
+            true,
+        );
+
+        let button_font = FontStyle {
+            location: Vector::new(10., 20.),
+            color: Color::WHITE,
+            ..input_font
+        };
+        let conf = ButtonConfig {
+            text: "Advanced".into(),
+            font_style: button_font.clone(),
+            background: button_background.clone(),
+            background_location: (Rectangle::new(
+                Vector::new(200., 260.),
+                Vector::new(110., 30.),
+            )),
+            blend_color: Some(Color::from_hex("008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
         };
-        let concealer = layer.add_widget(concealer_config);
+        let advanced_button = wrapper.context.add_widget(conf, &layer).unwrap();
 
         Ok(Login {
             _text,
             name_input,
             password_input,
             login_button,
-            _concealer: concealer,
-            server_address,
+            register_button,
+            advanced_button,
+            show_advanced: false,
+            server_picker,
+            server_status,
+            profile_name,
+            profile_url,
+            profile_buttons: None,
+            button_font,
+            button_background,
+            remember_me,
+            focus: FocusRing::new(),
+            error_font,
+            error: None,
         })
     }
+    async fn try_log_in(&mut self, wrapper: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        let username = self.name_input.get();
+        let password = self.password_input.get();
+        let new_address = wrapper.profiles.selected().url.clone();
+        let missing: Vec<_> = [
+            ("username", &username),
+            ("password", &password),
+            ("server address", &new_address),
+        ]
+        .iter()
+        .filter(|(_, value)| value.is_empty())
+        .map(|(name, _)| *name)
+        .collect();
+        if !missing.is_empty() {
+            self.error = Some(format!("Please fill in the {}.", missing.join(", ")));
+            return Ok(None);
+        }
+        match flow::log_in(&mut wrapper.client, username, password).await {
+            Ok(next) => {
+                match (self.remember_me.is_checked(), wrapper.client.authorization_code()) {
+                    (true, Some(token)) => saved::save_token(&wrapper.client.base_url, token.into())?,
+                    _ => saved::forget_token(&wrapper.client.base_url)?,
+                }
+                Ok(Some(show(next, wrapper).await?))
+            }
+            Err(x) => {
+                self.error = Some(describe_error(&x));
+                Ok(None)
+            }
+        }
+    }
+    fn profile_button(&self, wrapper: &mut Wrapper, text: &str, location: Rectangle) -> Response<BasicClickable> {
+        let mut layer = wrapper.context.add_layer();
+        layer.add_widget(ButtonConfig {
+            text: text.into(),
+            font_style: self.button_font.clone(),
+            background: self.button_background.clone(),
+            background_location: location,
+            blend_color: Some(Color::from_hex("#008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
+        })
+    }
+    fn profile_button_at(x: f32) -> Rectangle {
+        Rectangle::new(Vector::new(x, 425.), Vector::new(85., 30.))
+    }
+    fn toggle_advanced(&mut self, wrapper: &mut Wrapper) {
+        self.show_advanced = !self.show_advanced;
+        self.profile_buttons = if self.show_advanced {
+            Some(ProfileButtons {
+                add: self.profile_button(wrapper, "Add", Self::profile_button_at(200.)),
+                save: self.profile_button(wrapper, "Save", Self::profile_button_at(307.5)),
+                delete: self.profile_button(wrapper, "Delete", Self::profile_button_at(415.)),
+                clear_cache: self.profile_button(
+                    wrapper,
+                    "Clear cache",
+                    Rectangle::new(Vector::new(200., 460.), Vector::new(130., 30.)),
+                ),
+            })
+        } else {
+            self.profile_name.set_focused(false);
+            self.profile_url.set_focused(false);
+            None
+        };
+    }
+    fn edited_profile(&mut self) -> Option<ServerProfile> {
+        let profile = ServerProfile {
+            name: self.profile_name.get(),
+            url: self.profile_url.get(),
+        };
+        if profile.name.is_empty() || profile.url.is_empty() {
+            self.error = Some("A server profile needs both a name and an address.".into());
+            None
+        } else {
+            Some(profile)
+        }
+    }
+    //keeps the picker, the editing inputs and the client in line with whatever profile is selected
+    fn profiles_changed(&mut self, wrapper: &mut Wrapper) -> CResult<()> {
+        wrapper.profiles.save()?;
+        self.server_picker
+            .set_options(wrapper.profiles.names(), wrapper.profiles.selected_index());
+        let selected = wrapper.profiles.selected().clone();
+        self.profile_name.set(selected.name);
+        self.profile_url.set(selected.url.clone());
+        wrapper.client.set_base_url(selected.url);
+        Ok(())
+    }
+    //mergui sees clicks before we do, so buttons under an opened list have to be told to forget them
+    fn discard_profile_clicks(&mut self) {
+        if let Some(x) = &self.profile_buttons {
+            x.add.channel.has_clicked();
+            x.save.channel.has_clicked();
+            x.delete.channel.has_clicked();
+            x.clear_cache.channel.has_clicked();
+        }
+    }
+    fn profile_event(&mut self, wrapper: &mut Wrapper) -> CResult<()> {
+        let (add, save, delete, clear_cache) = match &self.profile_buttons {
+            Some(x) => (
+                x.add.channel.has_clicked(),
+                x.save.channel.has_clicked(),
+                x.delete.channel.has_clicked(),
+                x.clear_cache.channel.has_clicked(),
+            ),
+            None => return Ok(()),
+        };
+        if add {
+            if let Some(profile) = self.edited_profile() {
+                wrapper.profiles.add(profile);
+                self.profiles_changed(wrapper)?;
+            }
+        } else if save {
+            if let Some(profile) = self.edited_profile() {
+                wrapper.profiles.update_selected(profile);
+                self.profiles_changed(wrapper)?;
+            }
+        } else if delete {
+            if wrapper.profiles.remove_selected() {
+                self.profiles_changed(wrapper)?;
+            } else {
+                self.error = Some("The last server profile can not be deleted.".into());
+            }
+        } else if clear_cache {
+            if let Some(cache) = &mut wrapper.client.asset_cache {
+                cache.clear()?;
+            }
+        }
+        Ok(())
+    }
+    fn clear(&mut self) {
+        self.name_input.clear();
+        self.password_input.clear();
+        self.error = None;
+    }
 }
 
 #[async_trait(?Send)]
 impl Screen for Login {
     async fn draw(&mut self, wrapper: &mut Wrapper) -> CResult<()> {
         wrapper.gfx.clear(Color::WHITE);
+        self.name_input.draw(wrapper)?;
+        self.password_input.draw(wrapper)?;
+        self.remember_me.draw(wrapper)?;
+        if self.show_advanced {
+            self.profile_name.draw(wrapper)?;
+            self.profile_url.draw(wrapper)?;
+            let cached = wrapper
+                .client
+                .asset_cache
+                .as_ref()
+                .map(|v| v.size())
+                .unwrap_or(0);
+            self.error_font.draw(
+                &mut wrapper.gfx,
+                &format!(
+                    "{:.1} MB of card art cached ({})",
+                    cached as f32 / (1024. * 1024.),
+                    wrapper.client.cached_images.stats()
+                ),
+                Color::BLACK,
+                Vector::new(340., 480.),
+            )?;
+        }
+        //drawn last, so an opened list is on top of the other inputs
+        self.server_picker.draw(wrapper)?;
+        self.server_status.draw(wrapper)?;
+        if let Some(error) = &self.error {
+            self.error_font.draw(
+                &mut wrapper.gfx,
+                error,
+                Color::RED,
+                Vector::new(200., 185.),
+            )?;
+        }
         Ok(())
     }
-    async fn update(&mut self, _: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+    async fn update(&mut self, wrapper: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        //while editing a profile, show the status of the address being typed rather than the saved one
+        let url = if self.show_advanced {
+            self.profile_url.get()
+        } else {
+            wrapper.profiles.selected().url.clone()
+        };
+        self.server_status.update(&wrapper.client, url);
         Ok(None)
     }
     async fn event(
         &mut self,
         wrapper: &mut Wrapper,
-        _: &quicksilver::input::Event,
+        event: &quicksilver::input::Event,
     ) -> CResult<Option<Box<dyn Screen>>> {
-        if self.login_button.channel.has_clicked()
-            && self.password_input.channel.get() != ""
-            && self.name_input.channel.get() != ""
-            && self.server_address.channel.get() != ""
-        {
-            let new_address = self.server_address.channel.get();
-            if new_address != wrapper.client.base_url {
-                save(
-                    Location::Config,
-                    APP_NAME,
-                    "last_connected_server",
-                    &new_address,
-                )?;
-                wrapper.client.base_url = new_address;
+        //an opened list is drawn over the other inputs, so whatever it got clicked on belongs to it alone
+        match self.server_picker.event(event, wrapper) {
+            DropdownEvent::Picked(selected) => {
+                wrapper.profiles.select(selected);
+                self.profiles_changed(wrapper)?;
+                self.discard_profile_clicks();
+                return Ok(None);
             }
-            return match wrapper
-                .client
-                .log_in(
-                    self.name_input.channel.get(),
-                    self.password_input.channel.get(),
-                )
-                .await
-            {
-                Ok(_) => Ok(Some(Box::new(CharacterSelect::new(wrapper).await?))),
-                Err(_) => Ok(None),
-            };
+            DropdownEvent::Consumed => {
+                self.discard_profile_clicks();
+                return Ok(None);
+            }
+            DropdownEvent::Ignored => {}
+        }
+        if self.advanced_button.channel.has_clicked() {
+            self.toggle_advanced(wrapper);
+        }
+        self.profile_event(wrapper)?;
+        self.name_input.event(event, wrapper);
+        self.password_input.event(event, wrapper);
+        self.remember_me.event(event, wrapper);
+        if self.show_advanced {
+            self.profile_name.event(event, wrapper);
+            self.profile_url.event(event, wrapper);
+        }
+        let action = if self.show_advanced {
+            self.focus.event(
+                event,
+                &mut [
+                    &mut self.name_input,
+                    &mut self.password_input,
+                    &mut self.profile_name,
+                    &mut self.profile_url,
+                ],
+            )
+        } else {
+            self.focus
+                .event(event, &mut [&mut self.name_input, &mut self.password_input])
+        };
+        match action {
+            Some(FormAction::Submit) => return self.try_log_in(wrapper).await,
+            Some(FormAction::Cancel) => self.clear(),
+            None => {}
+        }
+        if self.login_button.channel.has_clicked() {
+            return self.try_log_in(wrapper).await;
+        }
+        if self.register_button.channel.has_clicked() {
+            return Ok(Some(Box::new(Register::new(wrapper).await?)));
         }
         Ok(None)
     }
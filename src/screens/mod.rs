@@ -2,9 +2,28 @@ pub(crate) mod battle;
 pub(crate) mod battle_over;
 pub(crate) mod char_select;
 pub(crate) mod login;
+pub(crate) mod preload;
+pub(crate) mod register;
 pub(crate) mod screen;
 pub(crate) use battle::Battle;
 pub(crate) use battle_over::BattleOver;
 pub(crate) use char_select::CharacterSelect;
-pub(crate) use login::Login;
+pub(crate) use login::{describe_error, Login};
+pub(crate) use preload::Preload;
+pub(crate) use register::Register;
 pub(crate) use screen::Screen;
+
+use crate::{flow::Step, Result, Wrapper};
+
+///Builds the screen that belongs to a step of the flow
+pub(crate) async fn show(step: Step, wrapper: &mut Wrapper) -> Result<Box<dyn Screen>> {
+    Ok(match step {
+        Step::Preload => Box::new(Preload::new(wrapper).await?),
+        Step::CharacterSelect { character } => Box::new(CharacterSelect::new(character, wrapper).await?),
+        Step::Battle {
+            character_id,
+            battle,
+        } => Box::new(Battle::new(character_id, battle, wrapper).await?),
+        Step::BattleOver => Box::new(BattleOver::new(wrapper).await?),
+    })
+}
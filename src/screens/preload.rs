@@ -0,0 +1,145 @@
+use super::{show, Screen};
+use crate::{client::PendingImage, flow, Result as CResult, Wrapper};
+use async_trait::async_trait;
+use mergui::{channels::BasicClickable, widgets::ButtonConfig, FontStyle, MFont, Response};
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, VectorFont},
+};
+
+//how many files are listed at once, the list scrolls along with the file being loaded
+const SHOWN_FILES: usize = 12;
+
+enum FileState {
+    Waiting,
+    Loaded,
+    Failed,
+}
+
+///Shown after logging in, loads the card art listed in the asset manifest of the server
+///so the first battle doesn't have to wait for it.
+pub(crate) struct Preload {
+    files: Vec<(String, FileState)>,
+    next: usize,
+    //the file at `next`, which is polled every frame so the progress gets drawn and skip stays clickable
+    loading: Option<PendingImage>,
+    font: MFont,
+    small_font: MFont,
+    skip: Response<BasicClickable>,
+}
+
+impl Preload {
+    pub(crate) async fn new(wrapper: &mut Wrapper) -> CResult<Self> {
+        //cards are still playable without their names and costs, so this isn't worth stopping over
+        let _ = wrapper.client.load_card_definitions().await;
+        //not every server has a manifest, in which case there is simply nothing to preload
+        let files = match wrapper.client.asset_manifest().await {
+            Ok(manifest) => manifest
+                .files
+                .into_iter()
+                .map(|v| (v, FileState::Waiting))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let ttf = VectorFont::load("font.ttf").await?;
+        let font = MFont::from_font(&ttf, &wrapper.gfx, 30.0)?;
+        let small_font = MFont::from_font(&ttf, &wrapper.gfx, 18.0)?;
+        let mut layer = wrapper.context.add_layer();
+        let skip = layer.add_widget(ButtonConfig {
+            text: "Skip".into(),
+            font_style: FontStyle {
+                font: MFont::from_font(&ttf, &wrapper.gfx, 20.0)?,
+                location: Vector::new(10., 20.),
+                color: Color::WHITE,
+            },
+            background: wrapper.button_image.clone(),
+            background_location: Rectangle::new(Vector::new(1083., 140.), Vector::new(100., 30.)),
+            blend_color: Some(Color::from_hex("#008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
+        });
+        Ok(Self {
+            files,
+            next: 0,
+            loading: None,
+            font,
+            small_font,
+            skip,
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl Screen for Preload {
+    async fn draw(&mut self, wrapper: &mut Wrapper) -> CResult<()> {
+        wrapper.gfx.clear(Color::WHITE);
+        self.font.draw(
+            &mut wrapper.gfx,
+            "Loading card art",
+            Color::BLACK,
+            Vector::new(183., 100.),
+        )?;
+        let bar = Rectangle::new(Vector::new(183., 140.), Vector::new(880., 30.));
+        let done = if self.files.is_empty() {
+            1.
+        } else {
+            self.next as f32 / self.files.len() as f32
+        };
+        wrapper.gfx.fill_rect(&bar, Color::from_hex("#D0D0D0"));
+        wrapper.gfx.fill_rect(
+            &Rectangle::new(bar.pos, Vector::new(bar.size.x * done, bar.size.y)),
+            Color::from_hex("#008B24"),
+        );
+        self.small_font.draw(
+            &mut wrapper.gfx,
+            &format!("{} / {}", self.next, self.files.len()),
+            Color::BLACK,
+            Vector::new(183., 195.),
+        )?;
+        let first = self.next.saturating_sub(SHOWN_FILES / 2);
+        for (i, (path, state)) in self.files.iter().enumerate().skip(first).take(SHOWN_FILES) {
+            let (status, color) = match state {
+                FileState::Waiting if i == self.next => ("loading", Color::BLACK),
+                FileState::Waiting => ("waiting", Color::from_hex("#746868")),
+                FileState::Loaded => ("done", Color::from_hex("#008B24")),
+                FileState::Failed => ("failed", Color::RED),
+            };
+            self.small_font.draw(
+                &mut wrapper.gfx,
+                &format!("{} \u{2014} {}", path, status),
+                color,
+                Vector::new(183., 230. + 25. * (i - first) as f32),
+            )?;
+        }
+        Ok(())
+    }
+    async fn update(&mut self, wrapper: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        if self.skip.channel.has_clicked() || self.next >= self.files.len() {
+            let next = flow::after_preload(&wrapper.client).await?;
+            return Ok(Some(show(next, wrapper).await?));
+        }
+        let (path, state) = &mut self.files[self.next];
+        let res = match &mut self.loading {
+            Some(pending) => wrapper.client.poll_preload(pending, &wrapper.gfx),
+            None => match wrapper.client.preload_image(path.clone()) {
+                Some(pending) => {
+                    self.loading = Some(pending);
+                    None
+                }
+                None => Some(Ok(())),
+            },
+        };
+        //still loading, which gets checked again next frame
+        let res = match res {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        //missing art shouldn't keep anyone out of the game, battles will try again when they need it
+        *state = match res {
+            Ok(()) => FileState::Loaded,
+            Err(_) => FileState::Failed,
+        };
+        self.loading = None;
+        self.next += 1;
+        Ok(None)
+    }
+}
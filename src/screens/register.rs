@@ -0,0 +1,179 @@
+use super::{describe_error, show, Login, Screen};
+use crate::{
+    flow,
+    screen_parts::{FocusRing, Focusable, FormAction, MaskConfig, TextInput, TextInputConfig},
+    Result as CResult, Wrapper,
+};
+use async_trait::async_trait;
+use mergui::{
+    channels::BasicClickable, core::Text, widgets::ButtonConfig, FontStyle, MFont, Response,
+};
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, VectorFont},
+};
+
+pub(crate) struct Register {
+    _text: Response<()>,
+    name_input: TextInput,
+    password_input: TextInput,
+    confirm_input: TextInput,
+    register_button: Response<BasicClickable>,
+    back_button: Response<BasicClickable>,
+    focus: FocusRing,
+    error_font: MFont,
+    error: Option<String>,
+}
+
+impl Register {
+    pub(crate) async fn new(wrapper: &mut Wrapper) -> CResult<Self> {
+        let layer = wrapper.context.add_layer();
+        let ttf = VectorFont::load("font.ttf").await?;
+        let font = MFont::from_font(&ttf, &wrapper.gfx, 30.0)?;
+        let error_font = MFont::from_font(&ttf, &wrapper.gfx, 18.0)?;
+        let conf = Text {
+            text: "Register".into(),
+            font_style: FontStyle {
+                font,
+                location: Vector::new(330., 40.),
+                color: Color::BLACK,
+            },
+        };
+        let _text = wrapper.context.add_widget(conf, &layer).unwrap();
+
+        let input_font = FontStyle {
+            font: MFont::from_font(&ttf, &wrapper.gfx, 20.0)?,
+            location: Vector::new(5., 19.),
+            color: Color::BLACK,
+        };
+        let placeholder_color = Color::from_hex("#746868");
+        let mut name_input = TextInput::new(TextInputConfig {
+            font: input_font.clone(),
+            placeholder: Some(("Username".into(), placeholder_color)),
+            location: Rectangle::new(Vector::new(200., 200.), Vector::new(300., 25.)),
+            start_value: None,
+            mask: None,
+        });
+        name_input.set_focused(true);
+        let password_input = TextInput::new(TextInputConfig {
+            font: input_font.clone(),
+            placeholder: Some(("Password".into(), placeholder_color)),
+            location: Rectangle::new(Vector::new(200., 230.), Vector::new(300., 25.)),
+            start_value: None,
+            mask: Some(MaskConfig { show_toggle: true }),
+        });
+        let confirm_input = TextInput::new(TextInputConfig {
+            font: input_font.clone(),
+            placeholder: Some(("Confirm password".into(), placeholder_color)),
+            location: Rectangle::new(Vector::new(200., 260.), Vector::new(300., 25.)),
+            start_value: None,
+            mask: Some(MaskConfig { show_toggle: false }),
+        });
+
+        let button_font = FontStyle {
+            location: Vector::new(10., 20.),
+            color: Color::WHITE,
+            ..input_font
+        };
+        let conf = ButtonConfig {
+            text: "Register".into(),
+            font_style: button_font.clone(),
+            background: wrapper.button_image.clone(),
+            background_location: Rectangle::new(Vector::new(390., 295.), Vector::new(110., 30.)),
+            blend_color: Some(Color::from_hex("#008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
+        };
+        let register_button = wrapper.context.add_widget(conf, &layer).unwrap();
+        let conf = ButtonConfig {
+            text: "Back".into(),
+            font_style: button_font,
+            background: wrapper.button_image.clone(),
+            background_location: Rectangle::new(Vector::new(200., 295.), Vector::new(70., 30.)),
+            blend_color: Some(Color::from_hex("#008B24")),
+            hover_color: Some(Color::from_hex("#07C739")),
+        };
+        let back_button = wrapper.context.add_widget(conf, &layer).unwrap();
+
+        Ok(Register {
+            _text,
+            name_input,
+            password_input,
+            confirm_input,
+            register_button,
+            back_button,
+            focus: FocusRing::new(),
+            error_font,
+            error: None,
+        })
+    }
+    async fn try_register(&mut self, wrapper: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        let username = self.name_input.get();
+        let password = self.password_input.get();
+        if username.is_empty() || password.is_empty() {
+            self.error = Some("Please fill in a username and password.".into());
+            return Ok(None);
+        }
+        if password != self.confirm_input.get() {
+            self.error = Some("The passwords do not match.".into());
+            return Ok(None);
+        }
+        match flow::register(&mut wrapper.client, username, password).await {
+            Ok(next) => Ok(Some(show(next, wrapper).await?)),
+            Err(x) => {
+                self.error = Some(describe_error(&x));
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Screen for Register {
+    async fn draw(&mut self, wrapper: &mut Wrapper) -> CResult<()> {
+        wrapper.gfx.clear(Color::WHITE);
+        self.name_input.draw(wrapper)?;
+        self.password_input.draw(wrapper)?;
+        self.confirm_input.draw(wrapper)?;
+        if let Some(error) = &self.error {
+            self.error_font.draw(
+                &mut wrapper.gfx,
+                error,
+                Color::RED,
+                Vector::new(200., 185.),
+            )?;
+        }
+        Ok(())
+    }
+    async fn update(&mut self, _: &mut Wrapper) -> CResult<Option<Box<dyn Screen>>> {
+        Ok(None)
+    }
+    async fn event(
+        &mut self,
+        wrapper: &mut Wrapper,
+        event: &quicksilver::input::Event,
+    ) -> CResult<Option<Box<dyn Screen>>> {
+        self.name_input.event(event, wrapper);
+        self.password_input.event(event, wrapper);
+        self.confirm_input.event(event, wrapper);
+        let action = self.focus.event(
+            event,
+            &mut [
+                &mut self.name_input,
+                &mut self.password_input,
+                &mut self.confirm_input,
+            ],
+        );
+        match action {
+            Some(FormAction::Submit) => return self.try_register(wrapper).await,
+            Some(FormAction::Cancel) => return Ok(Some(Box::new(Login::new(wrapper).await?))),
+            None => {}
+        }
+        if self.register_button.channel.has_clicked() {
+            return self.try_register(wrapper).await;
+        }
+        if self.back_button.channel.has_clicked() {
+            return Ok(Some(Box::new(Login::new(wrapper).await?)));
+        }
+        Ok(None)
+    }
+}